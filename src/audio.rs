@@ -2,7 +2,9 @@ use crate::dmx_types::{AudioAction, AudioTrack};
 use lofty::prelude::*;
 use parking_lot::Mutex;
 use rodio::{Decoder, DeviceSinkBuilder, Source};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -11,15 +13,31 @@ pub struct AudioEngine {
     ended_tracks: Arc<Mutex<Vec<(u32, AudioAction)>>>,
 }
 
+/// Top of the master volume range
+pub const MAX_MASTER_VOLUME: f32 = 1.0;
+
 struct ActivePlayback {
     track_id: u32,
     player: Arc<rodio::Player>,
     _sink: rodio::MixerDeviceSink,
     volume: f32,
     master_volume: f32,
+    trim_db: f32,
     action: AudioAction,
 }
 
+impl ActivePlayback {
+    fn output_volume(&self) -> f32 {
+        output_volume(self.volume, self.master_volume, self.trim_db)
+    }
+}
+
+/// Final player volume: `volume * master_volume * 10^(trim/20)`, clamped to unity gain
+/// so neither the master nor the trim can push a player into clipping
+fn output_volume(volume: f32, master_volume: f32, trim_db: f32) -> f32 {
+    (volume * master_volume * 10f32.powf(trim_db / 20.0)).clamp(0.0, 1.0)
+}
+
 impl AudioEngine {
     pub fn new() -> Result<Self, String> {
         Ok(Self {
@@ -49,8 +67,10 @@ impl AudioEngine {
     pub fn play(&self, track: &AudioTrack, master_volume: f32) -> Result<(), String> {
         self.stop(track.id);
 
-        let file =
-            File::open(&track.file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+        let file = File::open(&track.file_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("File not found: {}", track.file_path),
+            _ => format!("Failed to open file: {}", e),
+        })?;
 
         let source =
             Decoder::try_from(file).map_err(|e| format!("Failed to decode file: {}", e))?;
@@ -79,7 +99,7 @@ impl AudioEngine {
 
         if fade_in > 0.0 {
             println!("running fade in");
-            let vol = track.volume * master_volume;
+            let vol = output_volume(track.volume, master_volume, track.trim_db);
             tokio::spawn(async move {
                 println!("Starting fade");
                 let start_fade_in = Instant::now();
@@ -96,7 +116,7 @@ impl AudioEngine {
             });
         }
         if fade_out > 0.0 {
-            let vol = track.volume * master_volume;
+            let vol = output_volume(track.volume, master_volume, track.trim_db);
             let track_fade_out = track.fade_out.clone();
             let track_end = track.end_point.unwrap_or(track.duration);
             tokio::spawn(async move {
@@ -129,6 +149,7 @@ impl AudioEngine {
             _sink: sink,
             volume: track.volume,
             master_volume,
+            trim_db: track.trim_db,
             action: track.action.clone(),
         };
 
@@ -169,7 +190,7 @@ impl AudioEngine {
                 ended.push((p.track_id, p.action.clone()));
                 return false;
             }
-            p.player.set_volume(p.volume * p.master_volume);
+            p.player.set_volume(p.output_volume());
             true
         });
 
@@ -185,7 +206,7 @@ impl AudioEngine {
         let mut players = self.active_players.lock();
         for p in players.iter_mut() {
             p.master_volume = volume;
-            p.player.set_volume(p.volume * p.master_volume);
+            p.player.set_volume(p.output_volume());
         }
     }
     pub fn get_current_playback(&self) -> Vec<f32> {
@@ -209,4 +230,48 @@ impl AudioEngine {
             .iter()
             .any(|p| p.track_id == track_id && !p.player.empty() && !p.player.is_paused())
     }
+
+    /// Whether any track is currently audible
+    pub fn is_any_playing(&self) -> bool {
+        let players = self.active_players.lock();
+        players
+            .iter()
+            .any(|p| !p.player.empty() && !p.player.is_paused())
+    }
+}
+
+/// Playlist file: only the audio tracks with their settings, so one playlist
+/// can be loaded into any show
+#[derive(Serialize, Deserialize)]
+struct Playlist {
+    tracks: Vec<AudioTrack>,
+}
+
+pub fn save_playlist(tracks: &[AudioTrack], path: impl AsRef<Path>) -> Result<(), String> {
+    let playlist = Playlist {
+        tracks: tracks.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&playlist)
+        .map_err(|e| format!("Failed to write playlist: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to save playlist: {}", e))
+}
+
+/// Reads a playlist and re-fetches the durations in case the files changed.
+/// Tracks whose file no longer exists are kept; their names are returned alongside.
+pub fn load_playlist(path: impl AsRef<Path>) -> Result<(Vec<AudioTrack>, Vec<String>), String> {
+    let json =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to open playlist: {}", e))?;
+    let playlist: Playlist =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid playlist: {}", e))?;
+
+    let mut tracks = playlist.tracks;
+    let mut missing = Vec::new();
+    for track in &mut tracks {
+        if track.check_file() {
+            track.duration = AudioEngine::get_duration(&track.file_path);
+        } else {
+            missing.push(track.name.clone());
+        }
+    }
+    Ok((tracks, missing))
 }
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 
-use crate::dmx_types::{ChannelType, DMXBufferValue, Fixture};
-use open_dmx::DMX_CHANNELS;
+use crate::dmx_types::{
+    ChannelType, Color, Cue, CueNumber, DMXBufferValue, Fixture, ShutterMap, cct_to_cto_ctb,
+};
+use open_dmx::{DMX_CHANNELS, check_valid_channel};
 use scan_fmt::scan_fmt;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -39,6 +41,17 @@ pub enum ConsoleCommand {
         b: u8,
         w: u8,
     },
+    #[strum(serialize = "Fix {fixture_id} CCT {kelvin}")]
+    SetFixtureCct { fixture_id: u32, kelvin: u16 },
+    #[strum(serialize = "Fix {fixture_id} Prism {value}")]
+    SetFixturePrism { fixture_id: u32, value: u8 },
+    #[strum(serialize = "Fix {fixture_id} Frost {value}")]
+    SetFixtureFrost { fixture_id: u32, value: u8 },
+    /// Strobe frequency in Hz, 0 opens the shutter
+    #[strum(serialize = "Fix {fixture_id} Strobe {hz}")]
+    SetFixtureStrobe { fixture_id: u32, hz: f32 },
+    #[strum(serialize = "Fix {fixture_id} Shutter Closed")]
+    CloseFixtureShutter { fixture_id: u32 },
     #[strum(serialize = "Blackout")]
     Blackout,
     #[strum(serialize = "Clear")]
@@ -46,22 +59,40 @@ pub enum ConsoleCommand {
     #[strum(serialize = "Move Exec {exec_from} Cue {cue_from} To Exec {exec_to} Cue {cue_to}")]
     MoveExecCueToExecCue {
         exec_from: u32,
-        cue_from: u32,
+        cue_from: CueNumber,
         exec_to: u32,
-        cue_to: u32,
+        cue_to: CueNumber,
     },
     #[strum(serialize = "Move Exec {exec_from} Cue {cue_from} {direction}")]
     MoveExecCueDirection {
         exec_from: u32,
-        cue_from: u32,
+        cue_from: CueNumber,
         direction: Direction,
     },
+    #[strum(serialize = "All Strobe {value}")]
+    AllStrobe { value: u8 },
+    #[strum(serialize = "All Strobe Off")]
+    AllStrobeOff,
+    #[strum(serialize = "All At {value}")]
+    AllAt { value: u8 },
+    #[strum(serialize = "Store Exec {exec}")]
+    StoreExec { exec: u32 },
+    #[strum(serialize = "Store Exec {exec} Cue {cue}")]
+    StoreExecCue { exec: u32, cue: CueNumber },
+    #[strum(serialize = "Go Exec {exec}")]
+    GoExec { exec: u32 },
+    #[strum(serialize = "Back Exec {exec}")]
+    GoBackExec { exec: u32 },
+    #[strum(serialize = "Go Exec {exec} Cue {cue}")]
+    GoExecCue { exec: u32, cue: CueNumber },
+    #[strum(serialize = "Grab Intensity")]
+    GrabIntensity,
 }
 impl TryFrom<String> for ConsoleCommand {
     type Error = ConsoleError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let s = value.trim().to_lowercase();
+        let s = expand_level_keywords(&value.trim().to_lowercase());
         if s.eq_ignore_ascii_case("b/o")
             | s.eq_ignore_ascii_case("blackout")
             | s.eq_ignore_ascii_case("bo")
@@ -71,12 +102,68 @@ impl TryFrom<String> for ConsoleCommand {
         if s.eq_ignore_ascii_case("clear") | s.eq_ignore_ascii_case("clr") {
             return Ok(ConsoleCommand::Clear);
         }
+        if s == "grab" || s == "grab intensity" {
+            return Ok(ConsoleCommand::GrabIntensity);
+        }
+        if s == "all strobe off" {
+            return Ok(ConsoleCommand::AllStrobeOff);
+        }
+        if let Ok(value) = scan_fmt!(&s, "all strobe {}", u8) {
+            return Ok(ConsoleCommand::AllStrobe { value });
+        }
+        if let Ok(value) = scan_fmt!(&s, "all at {}", u8) {
+            return Ok(ConsoleCommand::AllAt { value });
+        }
+        if let Ok((exec, cue)) = scan_fmt!(&s, "store exec {} cue {}", u32, CueNumber) {
+            return Ok(ConsoleCommand::StoreExecCue { exec, cue });
+        }
+        if let Ok(exec) = scan_fmt!(&s, "store exec {}", u32) {
+            return Ok(ConsoleCommand::StoreExec { exec });
+        }
+        if let Ok((exec, cue)) = scan_fmt!(&s, "go exec {} cue {}", u32, CueNumber) {
+            return Ok(ConsoleCommand::GoExecCue { exec, cue });
+        }
+        if let Ok(exec) = scan_fmt!(&s, "go exec {}", u32) {
+            return Ok(ConsoleCommand::GoExec { exec });
+        }
+        if let Ok(exec) = scan_fmt!(&s, "back exec {}", u32) {
+            return Ok(ConsoleCommand::GoBackExec { exec });
+        }
         if let Ok((ch, value)) = scan_fmt!(&s, "chan {} at {}", usize, u8) {
             return Ok(ConsoleCommand::DimChannel { ch, value });
         }
         if let Ok((fixture_id, value)) = scan_fmt!(&s, "fix {} at {}", u32, u8) {
             return Ok(ConsoleCommand::DimFixture { fixture_id, value });
         }
+        if let Ok((fixture_id, kelvin)) = scan_fmt!(&s, "fix {} cct {}", u32, u16) {
+            return Ok(ConsoleCommand::SetFixtureCct { fixture_id, kelvin });
+        }
+        if let Ok(fixture_id) = scan_fmt!(&s, "fix {} strobe off", u32) {
+            return Ok(ConsoleCommand::SetFixtureStrobe {
+                fixture_id,
+                hz: 0.0,
+            });
+        }
+        if let Ok((fixture_id, hz)) = scan_fmt!(&s, "fix {} strobe {}", u32, f32)
+            && hz.is_finite()
+        {
+            return Ok(ConsoleCommand::SetFixtureStrobe { fixture_id, hz });
+        }
+        if let Ok(fixture_id) = scan_fmt!(&s, "fix {} shutter open", u32) {
+            return Ok(ConsoleCommand::SetFixtureStrobe {
+                fixture_id,
+                hz: 0.0,
+            });
+        }
+        if let Ok(fixture_id) = scan_fmt!(&s, "fix {} shutter closed", u32) {
+            return Ok(ConsoleCommand::CloseFixtureShutter { fixture_id });
+        }
+        if let Ok((fixture_id, value)) = scan_fmt!(&s, "fix {} prism {}", u32, u8) {
+            return Ok(ConsoleCommand::SetFixturePrism { fixture_id, value });
+        }
+        if let Ok((fixture_id, value)) = scan_fmt!(&s, "fix {} frost {}", u32, u8) {
+            return Ok(ConsoleCommand::SetFixtureFrost { fixture_id, value });
+        }
         if let Ok((fixture_id, r, g, b, w)) =
             scan_fmt!(&s, "fix {} color r{} g{} b{} w{}", u32, u8, u8, u8, u8)
         {
@@ -92,9 +179,9 @@ impl TryFrom<String> for ConsoleCommand {
             &s,
             "move exec {} cue {} to exec {} cue {}",
             u32,
+            CueNumber,
             u32,
-            u32,
-            u32
+            CueNumber
         ) {
             return Ok(ConsoleCommand::MoveExecCueToExecCue {
                 exec_from,
@@ -103,14 +190,15 @@ impl TryFrom<String> for ConsoleCommand {
                 cue_to,
             });
         }
-        if let Ok((exec_from, cue_from)) = scan_fmt!(&s, "move exec {} cue {} up", u32, u32) {
+        if let Ok((exec_from, cue_from)) = scan_fmt!(&s, "move exec {} cue {} up", u32, CueNumber) {
             return Ok(ConsoleCommand::MoveExecCueDirection {
                 exec_from,
                 cue_from,
                 direction: Direction::Up,
             });
         }
-        if let Ok((exec_from, cue_from)) = scan_fmt!(&s, "move exec {} cue {} down", u32, u32) {
+        if let Ok((exec_from, cue_from)) = scan_fmt!(&s, "move exec {} cue {} down", u32, CueNumber)
+        {
             return Ok(ConsoleCommand::MoveExecCueDirection {
                 exec_from,
                 cue_from,
@@ -120,17 +208,60 @@ impl TryFrom<String> for ConsoleCommand {
         Err(ConsoleError::UnknownCommand(value))
     }
 }
+/// Console mnemonics accepted after "at" in place of a DMX value
+const LEVEL_KEYWORDS: [(&str, u8); 4] = [("full", 255), ("half", 128), ("out", 0), ("zero", 0)];
+
+/// Replaces a trailing "at full/half/out/zero" with its numeric level
+fn expand_level_keywords(s: &str) -> String {
+    for (keyword, level) in LEVEL_KEYWORDS {
+        if let Some(target) = s.strip_suffix(keyword)
+            && let Some(target) = target.trim_end().strip_suffix(" at")
+        {
+            return format!("{target} at {level}");
+        }
+    }
+    s.to_string()
+}
+
 impl ConsoleCommand {
     pub fn parse(input: &str) -> Result<ConsoleCommand, ConsoleError> {
         ConsoleCommand::try_from(input.to_string())
     }
 }
 
+/// Runs the command line. Several commands can be separated by `;` or newlines,
+/// they run in order and stop at the first error, keeping what ran before it.
 pub fn execute_console_command(state: &mut crate::ConsoleState) {
-    let command = state.command_input.clone();
+    let input = state.command_input.clone();
+    execute_command_line(state, &input);
+}
+
+/// Runs `input` as a command line, see [`execute_console_command`].
+/// Errors are reported in `state.command_error`.
+pub fn execute_command_line(state: &mut crate::ConsoleState, input: &str) {
     state.command_error = None;
 
-    match ConsoleCommand::parse(&command) {
+    let commands: Vec<&str> = input
+        .split([';', '\n'])
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect();
+    match commands.as_slice() {
+        [] => return execute_command(state, input),
+        [command] => return execute_command(state, command),
+        _ => {}
+    }
+    for (idx, command) in commands.into_iter().enumerate() {
+        execute_command(state, command);
+        if let Some(error) = &state.command_error {
+            state.command_error = Some(format!("Command {} ({command}): {error}", idx + 1));
+            return;
+        }
+    }
+}
+
+fn execute_command(state: &mut crate::ConsoleState, command: &str) {
+    match ConsoleCommand::parse(command) {
         Ok(cmd) => match cmd {
             ConsoleCommand::Blackout => {
                 state.command_history.push(cmd);
@@ -211,46 +342,53 @@ pub fn execute_console_command(state: &mut crate::ConsoleState) {
                 b,
                 w,
             } => {
-                if let Some(fixture) = state.fixtures.iter_mut().find(|f| f.id == fixture_id) {
-                    if let Some(fixture_template) =
-                        state.template_library.get_template(fixture.template_id)
-                    {
-                        let values = fixture.get_fixture_as_buffer(fixture_template);
-
-                        for (chan_type, buf) in &values {
-                            if matches!(
-                                chan_type,
-                                ChannelType::Red
-                                    | ChannelType::Green
-                                    | ChannelType::Blue
-                                    | ChannelType::White
-                            ) {
-                                let new_value = match chan_type {
-                                    ChannelType::Red => r,
-                                    ChannelType::Green => g,
-                                    ChannelType::Blue => b,
-                                    ChannelType::White => w,
-                                    _ => continue,
-                                };
-                                if let Some(existing) =
-                                    state.buffer.iter_mut().find(|v| v.chan == buf.chan)
-                                {
-                                    existing.dmx = new_value;
-                                } else {
-                                    state.buffer.push(DMXBufferValue::new(buf.chan, new_value));
-                                }
-                            }
+                let available = fixture_channel_types(state, fixture_id);
+                let color = Color {
+                    w,
+                    ..Color::from_rgb(r, g, b)
+                };
+                let values = color.to_fixture_channels(&available);
+                let result = set_fixture_channels(state, fixture_id, "color", |t| {
+                    values.iter().find(|(c, _)| *c == t).map(|(_, v)| *v)
+                });
+                match result {
+                    Ok(fixture) => {
+                        if !available.contains(&ChannelType::White) {
+                            // Kept for the White slider, as before
+                            fixture.color.w = w;
                         }
-
-                        fixture.color.r = r;
-                        fixture.color.g = g;
-                        fixture.color.b = b;
-                        fixture.color.w = w;
-
                         state.command_history.push(cmd);
                     }
-                } else {
-                    state.command_error = Some(format!("Fixture {fixture_id} not found"));
+                    Err(e) => state.command_error = Some(e),
+                }
+            }
+            ConsoleCommand::SetFixtureCct { fixture_id, kelvin } => {
+                let (cto, ctb) = cct_to_cto_ctb(kelvin);
+                let result = set_fixture_channels(state, fixture_id, "CTO/CTB", |t| match t {
+                    ChannelType::CTO => Some(cto),
+                    ChannelType::CTB => Some(ctb),
+                    _ => None,
+                });
+                match result {
+                    Ok(fixture) => {
+                        fixture.cct = kelvin;
+                        state.command_history.push(cmd);
+                    }
+                    Err(e) => state.command_error = Some(e),
+                }
+            }
+            ConsoleCommand::SetFixturePrism { fixture_id, value }
+            | ConsoleCommand::SetFixtureFrost { fixture_id, value } => {
+                let channel_type = match cmd {
+                    ConsoleCommand::SetFixturePrism { .. } => ChannelType::Prism,
+                    _ => ChannelType::Frost,
+                };
+                let what = channel_type.name();
+                match set_fixture_channels(state, fixture_id, what, |t| {
+                    (t == channel_type).then_some(value)
+                }) {
+                    Ok(_) => state.command_history.push(cmd),
+                    Err(e) => state.command_error = Some(e),
                 }
             }
             ConsoleCommand::MoveExecCueToExecCue {
@@ -259,147 +397,568 @@ pub fn execute_console_command(state: &mut crate::ConsoleState) {
                 exec_to,
                 cue_to,
             } => {
-                let exec_idx_from = (exec_from.saturating_sub(1)) as usize;
-                let exec_idx_to = (exec_to.saturating_sub(1)) as usize;
-                let exec = &mut state.executors[exec_idx_from];
-                let cue_from_idx = exec
-                    .cue_list
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(idx, c)| if c.id == cue_from { Some(idx) } else { None })
-                    .next();
-                let exec = &mut state.executors[exec_idx_to];
-                let cue_to_idx = exec
-                    .cue_list
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(idx, c)| if c.id == cue_to { Some(idx) } else { None })
-                    .next();
-
-                if let Some(idx_from) = cue_from_idx {
-                    if let Some(idx_to) = cue_to_idx {
-                        if exec_from == exec_to {
-                            state.executors[exec_idx_from]
-                                .cue_list
-                                .swap(idx_from, idx_to);
-                        } else {
-                            let cue = state.executors[exec_idx_from].cue_list.remove(idx_from);
-                            let to_move = state.executors[exec_idx_to].cue_list.remove(idx_to);
-                            let mut found_same = false;
-                            // Prevent having same Cue ids from two different executors
-                            state.executors[exec_idx_to]
-                                .cue_list
-                                .iter_mut()
-                                .for_each(|c| {
-                                    if c.id == cue.id {
-                                        found_same = true
-                                    }
-                                    if found_same {
-                                        c.id += 1;
-                                    }
-                                });
-                            let mut found_same = false;
-                            // Prevent having same Cue ids from two different executors
-                            state.executors[exec_idx_from]
-                                .cue_list
-                                .iter_mut()
-                                .for_each(|c| {
-                                    if c.id == to_move.id {
-                                        found_same = true
-                                    }
-                                    if found_same {
-                                        c.id += 1;
-                                    }
-                                });
-                            state.executors[exec_idx_from]
-                                .cue_list
-                                .insert(idx_from, to_move);
-                            state.executors[exec_idx_to].cue_list.insert(idx_to, cue);
-                        }
-                    } else {
-                        let cue = state.executors[exec_idx_from].cue_list.remove(idx_from);
-                        let cue_size = state.executors[exec_idx_to].cue_list.len();
-                        if cue_size > 0 {
-                            let mut found_same = false;
-                            // Prevent having same Cue ids from two different executors
-                            state.executors[exec_idx_to]
-                                .cue_list
-                                .iter_mut()
-                                .for_each(|c| {
-                                    if c.id == cue.id {
-                                        found_same = true
-                                    }
-                                    if found_same {
-                                        c.id += 1;
-                                    }
-                                });
-                            state.executors[exec_idx_to]
-                                .cue_list
-                                .insert((cue_to.saturating_sub(1)) as usize, cue);
-                        } else {
-                            state.executors[exec_idx_to].cue_list.push(cue);
-                        }
-                    }
+                if let Some(exec) = [exec_from, exec_to]
+                    .into_iter()
+                    .find(|&e| executor_is_locked(state, e))
+                {
+                    state.command_error = Some(format!("Executor {exec} is locked"));
+                    return;
                 }
-                /* let exec_from = exec_from.saturating_sub(1);
-                let exec_to = exec_to.saturating_sub(1);
-                let same_exec = exec_from == exec_to;
-                let cue_from = cue_from.saturating_sub(1);
-                let cue_to = cue_to.saturating_sub(1);
-                if same_exec {
-                    let exec = &mut state.executors[exec_from];
-                    let current_cue = exec.cue_list[cue_from].clone();
-                    if let Some(cue_to_be_swapped) = exec.cue_list.get_mut(cue_to) {
-                        let cue_clone = cue_to_be_swapped.clone();
-                        *cue_to_be_swapped = current_cue;
-                        exec.cue_list[cue_from] = cue_clone;
-                    } else {
-                        let current_cue = exec.cue_list.remove(cue_from);
-                        exec.cue_list.insert(cue_to, current_cue);
-                    }
-                } else {
-                    let cue_a = state.executors[exec_from].cue_list.remove(cue_from);
-                    if let Some(cue) = state.executors[exec_to].cue_list.get_mut(cue_to) {
-                        let cue_b = cue.clone();
-                        *cue = cue_a.clone();
-                        state.executors[exec_from].cue_list.insert(cue_from, cue_b);
-                    } else {
-                        state.executors[exec_to].cue_list.insert(cue_to, cue_a);
-                    }
+                match move_cue(state, exec_from, cue_from, exec_to, cue_to) {
+                    Ok(()) => state.command_history.push(cmd),
+                    Err(e) => state.command_error = Some(e),
                 }
-
-                state.command_history.push(cmd); */
             }
             ConsoleCommand::MoveExecCueDirection {
                 exec_from,
                 cue_from,
                 direction,
             } => {
+                if executor_is_locked(state, exec_from) {
+                    state.command_error = Some(format!("Executor {exec_from} is locked"));
+                    return;
+                }
                 let exec_idx = (exec_from.saturating_sub(1)) as usize;
-                let cue_size = state.executors[exec_idx].cue_list.len();
-                if let Some(exec) = state.executors.get_mut(exec_idx) {
-                    let idx = exec
-                        .cue_list
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(idx, c)| if c.id == cue_from { Some(idx) } else { None })
-                        .next();
-                    if let Some(idx) = idx {
-                        exec.cue_list.swap(
-                            idx,
-                            match direction {
-                                Direction::Up => idx.saturating_add(1) % cue_size,
-                                Direction::Down => {
-                                    (cue_size as i16 + idx as i16 - 1) as usize % cue_size
-                                }
-                            },
-                        );
-                    }
+                if let Some(exec) = state.executors.get_mut(exec_idx)
+                    && let Some(idx) = exec.cue_index(cue_from)
+                {
+                    let cue_size = exec.cue_list.len();
+                    let other = match direction {
+                        Direction::Up => idx.saturating_add(1) % cue_size,
+                        Direction::Down => (cue_size + idx - 1) % cue_size,
+                    };
+                    // Cues keep their place in the numbering, so trade numbers
+                    let other_id = exec.cue_list[other].id;
+                    exec.cue_list[other].id = exec.cue_list[idx].id;
+                    exec.cue_list[idx].id = other_id;
+                    exec.sort_cues();
+                }
+            }
+            ConsoleCommand::CloseFixtureShutter { fixture_id }
+                if state.fixtures.iter().any(|f| f.id == fixture_id)
+                    && !fixture_shutter_map(state, fixture_id).can_close() =>
+            {
+                state.command_error = Some(format!(
+                    "Fixture {fixture_id} has no closed shutter value, set one in its template's shutter map"
+                ));
+            }
+            ConsoleCommand::SetFixtureStrobe { fixture_id, .. }
+            | ConsoleCommand::CloseFixtureShutter { fixture_id } => {
+                let map = fixture_shutter_map(state, fixture_id);
+                let value = match cmd {
+                    ConsoleCommand::SetFixtureStrobe { hz, .. } => map.strobe_value(hz),
+                    _ => map.closed,
+                };
+                let is_strobe = |t| matches!(t, ChannelType::Shutter | ChannelType::Strobe);
+                let result = set_fixture_channels(state, fixture_id, "shutter/strobe", |t| {
+                    is_strobe(t).then_some(value)
+                });
+                match result {
+                    Ok(_) => state.command_history.push(cmd),
+                    Err(e) => state.command_error = Some(e),
+                }
+            }
+            ConsoleCommand::AllStrobe { value } => {
+                let is_strobe = |t| matches!(t, ChannelType::Shutter | ChannelType::Strobe);
+                if set_matching_channels(state, is_strobe, value) == 0 {
+                    state.command_error = Some("No fixture with a shutter/strobe channel".into());
+                } else {
+                    state.command_history.push(cmd);
+                }
+            }
+            ConsoleCommand::AllStrobeOff => {
+                let is_strobe = |t| matches!(t, ChannelType::Shutter | ChannelType::Strobe);
+                set_matching_channels(state, is_strobe, 0);
+                state.command_history.push(cmd);
+            }
+            ConsoleCommand::GrabIntensity => {
+                if grab_intensities(state) == 0 {
+                    state.command_error = Some("No patched fixture".into());
+                } else {
+                    state.command_history.push(cmd);
+                }
+            }
+            ConsoleCommand::AllAt { value } => {
+                if set_all_intensity(state, value) == 0 {
+                    state.command_error = Some("No patched fixture".into());
+                } else {
+                    state.command_history.push(cmd);
+                }
+            }
+            ConsoleCommand::StoreExec { exec } | ConsoleCommand::StoreExecCue { exec, .. } => {
+                let cue = match cmd {
+                    ConsoleCommand::StoreExecCue { cue, .. } => Some(cue),
+                    _ => None,
+                };
+                match store_buffer_to_executor(state, exec.saturating_sub(1) as usize, cue) {
+                    Ok(_) => state.command_history.push(cmd),
+                    Err(e) => state.command_error = Some(e),
                 }
             }
+            ConsoleCommand::GoExec { exec } | ConsoleCommand::GoBackExec { exec } => {
+                let Some(executor) = state.executors.get_mut(exec.saturating_sub(1) as usize)
+                else {
+                    state.command_error = Some(format!("Executor {exec} not found"));
+                    return;
+                };
+                if matches!(cmd, ConsoleCommand::GoExec { .. }) {
+                    executor.go();
+                } else {
+                    executor.go_back();
+                }
+                state.command_history.push(cmd);
+            }
+            ConsoleCommand::GoExecCue { exec, cue } => {
+                let Some(executor) = state.executors.get_mut(exec.saturating_sub(1) as usize)
+                else {
+                    state.command_error = Some(format!("Executor {exec} not found"));
+                    return;
+                };
+                let Some(idx) = executor.cue_index(cue) else {
+                    state.command_error = Some(format!("Cue {cue} not found in executor {exec}"));
+                    return;
+                };
+                executor.go_to(idx);
+                state.command_history.push(cmd);
+            }
         },
         Err(e) => {
             state.command_error = Some(e.to_string());
         }
     }
 }
+
+/// Moves a cue to another number, possibly in another executor. Moving onto an
+/// existing cue swaps the two, moving to a free number (like 2.5) renumbers the cue.
+fn move_cue(
+    state: &mut crate::ConsoleState,
+    exec_from: u32,
+    cue_from: CueNumber,
+    exec_to: u32,
+    cue_to: CueNumber,
+) -> Result<(), String> {
+    let exec_idx_from = (exec_from.saturating_sub(1)) as usize;
+    let exec_idx_to = (exec_to.saturating_sub(1)) as usize;
+    for exec in [exec_from, exec_to] {
+        if state
+            .executors
+            .get(exec.saturating_sub(1) as usize)
+            .is_none()
+        {
+            return Err(format!("Executor {exec} not found"));
+        }
+    }
+    let Some(idx_from) = state.executors[exec_idx_from].cue_index(cue_from) else {
+        return Err(format!("Cue {cue_from} not found in executor {exec_from}"));
+    };
+    let idx_to = state.executors[exec_idx_to].cue_index(cue_to);
+
+    if exec_idx_from == exec_idx_to {
+        let exec = &mut state.executors[exec_idx_from];
+        if let Some(idx_to) = idx_to {
+            exec.cue_list[idx_to].id = cue_from;
+        }
+        exec.cue_list[idx_from].id = cue_to;
+        exec.sort_cues();
+        return Ok(());
+    }
+
+    let mut cue = state.executors[exec_idx_from].remove_cue(idx_from);
+    if let Some(idx_to) = idx_to {
+        let mut swapped = state.executors[exec_idx_to].remove_cue(idx_to);
+        swapped.id = cue_from;
+        state.executors[exec_idx_from].insert_cue(swapped)?;
+    }
+    cue.id = cue_to;
+    state.executors[exec_idx_to].insert_cue(cue)
+}
+
+/// Moves several cues of one executor to another, keeping their relative order.
+/// Their numbers are kept when all are free in the destination, otherwise they are
+/// renumbered after its last cue.
+pub fn move_cues(
+    state: &mut crate::ConsoleState,
+    exec_from: u32,
+    cues: &[CueNumber],
+    exec_to: u32,
+) -> Result<(), String> {
+    if let Some(exec) = [exec_from, exec_to]
+        .into_iter()
+        .find(|&e| executor_is_locked(state, e))
+    {
+        return Err(format!("Executor {exec} is locked"));
+    }
+    if exec_from == exec_to {
+        return Err(format!("The cues are already in executor {exec_to}"));
+    }
+    let exec_idx_from = (exec_from.saturating_sub(1)) as usize;
+    let exec_idx_to = (exec_to.saturating_sub(1)) as usize;
+    for exec in [exec_from, exec_to] {
+        if state
+            .executors
+            .get(exec.saturating_sub(1) as usize)
+            .is_none()
+        {
+            return Err(format!("Executor {exec} not found"));
+        }
+    }
+    if let Some(missing) = cues
+        .iter()
+        .find(|&&cue| state.executors[exec_idx_from].cue_index(cue).is_none())
+    {
+        return Err(format!("Cue {missing} not found in executor {exec_from}"));
+    }
+
+    let mut moved: Vec<_> = cues
+        .iter()
+        .filter_map(|&cue| {
+            let idx = state.executors[exec_idx_from].cue_index(cue)?;
+            Some(state.executors[exec_idx_from].remove_cue(idx))
+        })
+        .collect();
+    moved.sort_by_key(|cue| cue.id);
+
+    let destination = &mut state.executors[exec_idx_to];
+    let clashes = moved
+        .iter()
+        .any(|cue| destination.cue_index(cue.id).is_some());
+    for mut cue in moved {
+        if clashes {
+            cue.id = destination.next_cue_number()?;
+        }
+        destination.insert_cue(cue)?;
+    }
+    Ok(())
+}
+
+/// Stores the buffer in executor `exec_idx` (0-based) as cue `cue`, or as the next whole
+/// cue number when `None`. An existing cue with that number gets the buffer as its levels,
+/// keeping its name and timing. Returns the stored cue number.
+pub fn store_buffer_to_executor(
+    state: &mut crate::ConsoleState,
+    exec_idx: usize,
+    cue: Option<CueNumber>,
+) -> Result<CueNumber, String> {
+    let Some(exec) = state.executors.get_mut(exec_idx) else {
+        return Err(format!("Executor {} not found", exec_idx + 1));
+    };
+    if exec.locked {
+        return Err(format!("Executor {} is locked", exec_idx + 1));
+    }
+    let mut levels = vec![0; DMX_CHANNELS];
+    for val in &state.buffer {
+        if check_valid_channel(val.chan).is_ok() {
+            levels[val.chan.saturating_sub(1)] = val.dmx;
+        }
+    }
+    let number = match cue {
+        Some(number) => number,
+        None => exec.next_cue_number()?,
+    };
+    if let Some(idx) = exec.cue_index(number) {
+        exec.cue_list[idx].levels = levels;
+        if idx == exec.current_cue_index {
+            exec.stored_channels = exec.cue_list[idx].levels.clone();
+        }
+    } else {
+        let mut new_cue = Cue::new(number);
+        new_cue.levels = levels;
+        exec.insert_cue(new_cue)?;
+    }
+    Ok(number)
+}
+
+/// Channel types of a fixture's mode, empty when the fixture or its template is missing
+fn fixture_channel_types(state: &crate::ConsoleState, fixture_id: u32) -> Vec<ChannelType> {
+    state
+        .fixtures
+        .iter()
+        .find(|f| f.id == fixture_id)
+        .and_then(|f| {
+            state
+                .template_library
+                .get_template(f.template_id)
+                .and_then(|t| t.get_mode(f.mode_index))
+        })
+        .map(|mode| mode.channels.iter().map(|c| c.channel_type).collect())
+        .unwrap_or_default()
+}
+
+/// Shutter mapping of a fixture's template, the default one when the fixture is missing
+fn fixture_shutter_map(state: &crate::ConsoleState, fixture_id: u32) -> ShutterMap {
+    state
+        .fixtures
+        .iter()
+        .find(|f| f.id == fixture_id)
+        .and_then(|f| state.template_library.get_template(f.template_id))
+        .map(|t| t.shutter_map())
+        .unwrap_or_default()
+}
+
+/// Writes the value chosen by `value_for` to each matching channel of one fixture,
+/// on the fixture and in the buffer. `what` names the channels in the error message.
+fn set_fixture_channels<'a>(
+    state: &'a mut crate::ConsoleState,
+    fixture_id: u32,
+    what: &str,
+    value_for: impl Fn(ChannelType) -> Option<u8>,
+) -> Result<&'a mut Fixture, String> {
+    let Some(fixture) = state.fixtures.iter_mut().find(|f| f.id == fixture_id) else {
+        return Err(format!("Fixture {fixture_id} not found"));
+    };
+    let Some(mode) = state
+        .template_library
+        .get_template(fixture.template_id)
+        .and_then(|t| t.get_mode(fixture.mode_index))
+    else {
+        return Err(format!("Fixture {fixture_id} has no template"));
+    };
+    let mut found = false;
+    for chan_def in &mode.channels {
+        let Some(value) = value_for(chan_def.channel_type) else {
+            continue;
+        };
+        fixture.set_channel_value(chan_def, value);
+        set_buffer_value(
+            &mut state.buffer,
+            fixture.start_channel + chan_def.offset as usize,
+            value,
+        );
+        found = true;
+    }
+    if found {
+        Ok(fixture)
+    } else {
+        Err(format!("Fixture {fixture_id} has no {what} channel"))
+    }
+}
+
+/// Writes `value` to every patched channel whose type matches, on the fixtures and in the buffer.
+/// Returns how many fixtures had a matching channel.
+fn set_matching_channels(
+    state: &mut crate::ConsoleState,
+    matches: impl Fn(ChannelType) -> bool,
+    value: u8,
+) -> usize {
+    let mut touched = 0;
+    for fixture in state.fixtures.iter_mut() {
+        let Some(mode) = state
+            .template_library
+            .get_template(fixture.template_id)
+            .and_then(|t| t.get_mode(fixture.mode_index))
+        else {
+            continue;
+        };
+        let mut found = false;
+        for chan_def in mode.channels.iter().filter(|c| matches(c.channel_type)) {
+            fixture.set_channel_value(chan_def, value);
+            set_buffer_value(
+                &mut state.buffer,
+                fixture.start_channel + chan_def.offset as usize,
+                value,
+            );
+            found = true;
+        }
+        if found {
+            touched += 1;
+        }
+    }
+    touched
+}
+
+/// Whether the executor with the given 1-based number exists and is locked
+fn executor_is_locked(state: &crate::ConsoleState, exec: u32) -> bool {
+    state
+        .executors
+        .get(exec.saturating_sub(1) as usize)
+        .is_some_and(|e| e.locked)
+}
+
+/// Sets every patched fixture to `value` through its Intensity channel. Fixtures without
+/// one are dimmed through their color channels, and black fixtures go white at any level
+/// above 0 so they light up. Returns how many fixtures were set.
+fn set_all_intensity(state: &mut crate::ConsoleState, value: u8) -> usize {
+    let is_color = |t| {
+        matches!(
+            t,
+            ChannelType::Red | ChannelType::Green | ChannelType::Blue | ChannelType::White
+        )
+    };
+    let mut touched = 0;
+    for fixture in state.fixtures.iter_mut() {
+        let Some(template) = state.template_library.get_template(fixture.template_id) else {
+            continue;
+        };
+        let Some(mode) = template.get_mode(fixture.mode_index) else {
+            continue;
+        };
+        let has_intensity = mode
+            .channels
+            .iter()
+            .any(|c| c.channel_type == ChannelType::Intensity);
+        let set_color = !has_intensity || (value > 0 && !fixture.color.has_color());
+        let sets = |t: ChannelType| t == ChannelType::Intensity || (set_color && is_color(t));
+
+        for chan_def in mode.channels.iter().filter(|c| sets(c.channel_type)) {
+            fixture.set_channel_value(chan_def, value);
+        }
+        // Buffer what the fixture now outputs on those channels, intensity limits included
+        for (chan_type, buf) in fixture.get_fixture_as_buffer(template) {
+            if sets(chan_type) {
+                set_buffer_value(&mut state.buffer, buf.chan, buf.dmx);
+            }
+        }
+        touched += 1;
+    }
+    touched
+}
+
+/// Copies the intensity every enabled fixture outputs into the buffer, so a live
+/// look can be tweaked and stored. Fixtures without an intensity channel give
+/// their color channels instead. Returns the number of fixtures grabbed.
+pub fn grab_intensities(state: &mut crate::ConsoleState) -> usize {
+    let mut grabbed = 0;
+    for fixture in state.fixtures.iter().filter(|f| f.enabled) {
+        let Some(template) = state.template_library.get_template(fixture.template_id) else {
+            continue;
+        };
+        let values = fixture.get_fixture_as_buffer(template);
+        let has_intensity = values.iter().any(|(t, _)| *t == ChannelType::Intensity);
+        let grabs = |t: ChannelType| match t {
+            ChannelType::Intensity => true,
+            ChannelType::Red | ChannelType::Green | ChannelType::Blue | ChannelType::White => {
+                !has_intensity
+            }
+            _ => false,
+        };
+        for (chan_type, buf) in values {
+            if grabs(chan_type) {
+                set_buffer_value(&mut state.buffer, buf.chan, buf.dmx);
+            }
+        }
+        grabbed += 1;
+    }
+    grabbed
+}
+
+/// Sets a channel in the buffer, adding it if it's not there yet
+pub fn set_buffer_value(buffer: &mut Vec<DMXBufferValue>, chan: usize, value: u8) {
+    if let Some(existing) = buffer.iter_mut().find(|v| v.chan == chan) {
+        existing.dmx = value;
+    } else {
+        buffer.push(DMXBufferValue::new(chan, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel_level(input: &str) -> Option<u8> {
+        match ConsoleCommand::parse(input) {
+            Ok(ConsoleCommand::DimChannel { ch: 5, value }) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn fixture_level(input: &str) -> Option<u8> {
+        match ConsoleCommand::parse(input) {
+            Ok(ConsoleCommand::DimFixture {
+                fixture_id: 3,
+                value,
+            }) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn full_is_255() {
+        assert_eq!(channel_level("chan 5 at full"), Some(255));
+        assert_eq!(fixture_level("fix 3 at full"), Some(255));
+    }
+
+    #[test]
+    fn half_is_128() {
+        assert_eq!(channel_level("chan 5 at half"), Some(128));
+        assert_eq!(fixture_level("fix 3 at half"), Some(128));
+    }
+
+    #[test]
+    fn out_is_0() {
+        assert_eq!(channel_level("chan 5 at out"), Some(0));
+        assert_eq!(fixture_level("fix 3 at out"), Some(0));
+    }
+
+    #[test]
+    fn zero_is_0() {
+        assert_eq!(channel_level("chan 5 at zero"), Some(0));
+        assert_eq!(fixture_level("fix 3 at zero"), Some(0));
+    }
+
+    #[test]
+    fn keywords_ignore_case_and_spacing() {
+        assert_eq!(channel_level("  Chan 5 AT Full "), Some(255));
+    }
+
+    #[test]
+    fn trailing_separator_runs_the_single_command() {
+        let mut state = crate::ConsoleState::without_hardware();
+        execute_command_line(&mut state, "chan 1 at 255;");
+        assert_eq!(state.command_error, None);
+        assert_eq!(state.buffer[0].chan, 1);
+        assert_eq!(state.buffer[0].dmx, 255);
+    }
+
+    #[test]
+    fn failing_command_is_numbered_across_separators() {
+        let mut state = crate::ConsoleState::without_hardware();
+        execute_command_line(&mut state, "chan 1 at 10; chan 2 at 20\nbogus");
+        let error = state.command_error.unwrap();
+        assert!(error.starts_with("Command 3 (bogus)"), "{error}");
+        assert_eq!(state.buffer.len(), 2);
+    }
+
+    #[test]
+    fn shutter_close_needs_a_closed_value() {
+        let mut state = crate::ConsoleState::without_hardware();
+        let template_id = state
+            .template_library
+            .templates
+            .iter()
+            .find(|t| t.name == "Generic Strobe")
+            .unwrap()
+            .id;
+        state
+            .fixtures
+            .push(Fixture::new(1, "Strobe".into(), 1, template_id, 0));
+
+        execute_command_line(&mut state, "fix 1 shutter closed");
+        assert!(state.command_error.is_some());
+        assert!(state.command_history.is_empty());
+
+        let template = state
+            .template_library
+            .get_template_mut(template_id)
+            .unwrap();
+        template.shutter_map = Some(ShutterMap {
+            closed: 255,
+            ..ShutterMap::default()
+        });
+        execute_command_line(&mut state, "fix 1 shutter closed");
+        assert_eq!(state.command_error, None);
+        assert_eq!(state.buffer[0].dmx, 255);
+    }
+
+    #[test]
+    fn unknown_keyword_is_unknown_command() {
+        for input in ["chan 5 at bright", "fix 3 at max", "chan 5 full"] {
+            assert!(
+                matches!(
+                    ConsoleCommand::parse(input),
+                    Err(ConsoleError::UnknownCommand(command)) if command == input
+                ),
+                "{input}"
+            );
+        }
+    }
+}
@@ -1,28 +1,54 @@
-use crate::dmx_types::{FadeDirection, DMX_CHANNELS};
+use std::time::Instant;
+
+use crate::dmx_types::{ChannelType, DMX_CHANNELS};
+
+/// What the DMX output does while frozen
+#[derive(PartialEq, Default, Clone, Copy, Debug)]
+pub enum FreezeMode {
+    /// Keep sending the held frame, fixtures stay where they are
+    #[default]
+    Hold,
+    /// Stop sending frames, fixtures fall back to their signal-loss behaviour
+    StopSending,
+}
+
+impl FreezeMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FreezeMode::Hold => "Hold output",
+            FreezeMode::StopSending => "Stop sending",
+        }
+    }
+}
 
 pub fn mix_executor_outputs(state: &mut crate::ui::ConsoleState) {
+    // While holding, the agent resends the held frame by itself
+    set_continuous_output(state, state.frozen && state.freeze_mode == FreezeMode::Hold);
+    if state.frozen {
+        // Faders, cues and the buffer are ignored until unfrozen, state.channels is held
+        check_dmx_agent(state);
+        return;
+    }
     let mut dmx_chans = [0u8; DMX_CHANNELS];
 
     // Calculate the executors values
     state.executors.iter_mut().for_each(|exec| {
         exec.update_fade();
+        exec.update_follow();
+        // Grand master scaled by the executor's submaster, if assigned
+        let master = state.master_dimmer
+            * exec
+                .submaster
+                .and_then(|sub| state.submasters.get(sub as usize))
+                .copied()
+                .unwrap_or(1.0);
         if exec.fader_level > 0.0 {
             if let Some(current_cue) = &exec.cue_list.get(exec.current_cue_index) {
                 // Check if we should interpolate (fading and direction is set)
                 if exec.is_fading {
-                    if let Some(direction) = exec.last_direction {
-                        // Calculate previous cue index based on direction
-                        let prev_cue_idx = match direction {
-                            FadeDirection::Positive => {
-                                (exec.current_cue_index + exec.cue_list.len() - 1)
-                                    % exec.cue_list.len()
-                            }
-                            FadeDirection::Negative => {
-                                (exec.current_cue_index + 1) % exec.cue_list.len()
-                            }
-                        };
-
-                        if let Some(prev_cue) = exec.cue_list.get(prev_cue_idx) {
+                    if exec.last_direction.is_some() {
+                        // Crossfade from the cue GO left, disabled cues may have been skipped
+                        if let Some(prev_cue) = exec.cue_list.get(exec.fade_from_index) {
                             let progress = exec.current_output_level;
 
                             for (idx, cue_dmx_level) in current_cue.levels.iter().enumerate() {
@@ -30,7 +56,7 @@ pub fn mix_executor_outputs(state: &mut crate::ui::ConsoleState) {
                                 let curr_level = *cue_dmx_level as f32;
                                 let interpolated =
                                     prev_level + (curr_level - prev_level) * progress;
-                                dmx_chans[idx] = (interpolated * state.master_dimmer) as u8;
+                                dmx_chans[idx] = (interpolated * master) as u8;
                             }
                         }
                     } else {
@@ -40,10 +66,9 @@ pub fn mix_executor_outputs(state: &mut crate::ui::ConsoleState) {
                             .iter()
                             .enumerate()
                             .for_each(|(idx, cue_dmx_level)| {
-                                dmx_chans[idx] = ((*cue_dmx_level as f32
-                                    * exec.current_output_level)
-                                    * state.master_dimmer)
-                                    as u8;
+                                dmx_chans[idx] =
+                                    ((*cue_dmx_level as f32 * exec.current_output_level) * master)
+                                        as u8;
                             });
                     }
                 } else {
@@ -54,8 +79,7 @@ pub fn mix_executor_outputs(state: &mut crate::ui::ConsoleState) {
                         .enumerate()
                         .for_each(|(idx, cue_dmx_level)| {
                             dmx_chans[idx] = ((*cue_dmx_level as f32 * exec.current_output_level)
-                                * state.master_dimmer)
-                                as u8;
+                                * master) as u8;
                         });
                 }
             }
@@ -69,21 +93,280 @@ pub fn mix_executor_outputs(state: &mut crate::ui::ConsoleState) {
         }
     });
 
+    smooth_movement(state, &mut dmx_chans);
+
+    // Fixture intensity limits bound the final output, whatever cues and buffer asked for
+    for fixture in state.fixtures.iter().filter(|f| f.has_intensity_limits()) {
+        let Some(mode) = state
+            .template_library
+            .get_template(fixture.template_id)
+            .and_then(|t| t.get_mode(fixture.mode_index))
+        else {
+            continue;
+        };
+        for chan_def in mode
+            .channels
+            .iter()
+            .filter(|c| c.channel_type == ChannelType::Intensity)
+        {
+            let idx = (fixture.start_channel + chan_def.offset as usize).saturating_sub(1);
+            if let Some(chan) = dmx_chans.get_mut(idx) {
+                *chan = fixture.limit_intensity(*chan);
+            }
+        }
+    }
+
+    // Disabled fixtures are bypassed, cues and buffer included
+    for fixture in state.fixtures.iter().filter(|f| !f.enabled) {
+        let Some(mode) = state
+            .template_library
+            .get_template(fixture.template_id)
+            .and_then(|t| t.get_mode(fixture.mode_index))
+        else {
+            continue;
+        };
+        for chan_def in &mode.channels {
+            let idx = (fixture.start_channel + chan_def.offset as usize).saturating_sub(1);
+            if let Some(chan) = dmx_chans.get_mut(idx) {
+                *chan = 0;
+            }
+        }
+    }
+
     if dmx_chans.to_vec() != state.channels {
         state.channels = dmx_chans.to_vec().clone();
-        println!("Channels updated");
         if let Some(dmx) = &mut state.dmx_serial {
             dmx.set_channels(dmx_chans);
+            // Ask the serial agent to push the new frame, a failure means the dongle is gone
+            match dmx.update_async() {
+                Ok(()) => println!("Channels updated"),
+                Err(e) => {
+                    state.dmx_connected = false;
+                    state.dmx_serial_error = e.to_string();
+                }
+            }
         }
     }
+    check_dmx_agent(state);
+}
+
+/// Longest pan/tilt move time that can be configured, in seconds
+pub const MAX_MOVE_TIME: f32 = 10.0;
+
+/// Pan/tilt ramp of one fixture towards the position last set in the buffer
+pub struct MoveRamp {
+    from: [f32; 2],
+    target: [u16; 2],
+    current: [f32; 2],
+    started: Instant,
+}
+
+/// DMX channel indexes (0-based) of a fixture's [Pan, PanFine, Tilt, TiltFine]
+fn position_channels(
+    state: &crate::ui::ConsoleState,
+    fixture: &crate::dmx_types::Fixture,
+) -> [Option<usize>; 4] {
+    let mut chans = [None; 4];
+    let Some(mode) = state
+        .template_library
+        .get_template(fixture.template_id)
+        .and_then(|t| t.get_mode(fixture.mode_index))
+    else {
+        return chans;
+    };
+    for chan_def in &mode.channels {
+        let slot = match chan_def.channel_type {
+            ChannelType::Pan => 0,
+            ChannelType::PanFine => 1,
+            ChannelType::Tilt => 2,
+            ChannelType::TiltFine => 3,
+            _ => continue,
+        };
+        chans[slot] = Some((fixture.start_channel + chan_def.offset as usize).saturating_sub(1));
+    }
+    chans
+}
+
+/// Reads [pan, tilt] as 16 bit values, a missing fine channel counting as 0
+fn read_position(chans: &[Option<usize>; 4], dmx: &[u8]) -> [u16; 2] {
+    let value = |idx: Option<usize>| idx.and_then(|i| dmx.get(i)).copied().unwrap_or(0) as u16;
+    [
+        value(chans[0]) << 8 | value(chans[1]),
+        value(chans[2]) << 8 | value(chans[3]),
+    ]
+}
+
+fn write_position(chans: &[Option<usize>; 4], dmx: &mut [u8], position: [f32; 2]) {
+    for (axis, value) in position.iter().enumerate() {
+        let value = value.round() as u16;
+        let mut write = |idx: Option<usize>, byte: u8| {
+            if let Some(chan) = idx.and_then(|i| dmx.get_mut(i)) {
+                *chan = byte;
+            }
+        };
+        write(chans[axis * 2], (value >> 8) as u8);
+        write(chans[axis * 2 + 1], (value & 0xFF) as u8);
+    }
+}
+
+/// Ramps pan/tilt set directly in the buffer over `state.move_time` instead of jumping.
+/// Cue positions keep their own fade timing, a move time of 0 moves instantly.
+fn smooth_movement(state: &mut crate::ui::ConsoleState, dmx_chans: &mut [u8; DMX_CHANNELS]) {
+    if state.move_time <= 0.0 {
+        state.move_ramps.clear();
+        return;
+    }
+    let now = Instant::now();
+    let mut moved = std::collections::HashSet::new();
+    for fixture in &state.fixtures {
+        let chans = position_channels(state, fixture);
+        let in_buffer = chans.iter().flatten().any(|idx| {
+            state
+                .buffer
+                .iter()
+                .any(|v| v.chan.saturating_sub(1) == *idx)
+        });
+        if !in_buffer {
+            continue;
+        }
+        moved.insert(fixture.id);
+
+        let target = read_position(&chans, dmx_chans);
+        let ramp = state.move_ramps.entry(fixture.id).or_insert_with(|| {
+            // Start from wherever the fixture was last sent
+            let previous = read_position(&chans, &state.channels).map(|v| v as f32);
+            MoveRamp {
+                from: previous,
+                target,
+                current: previous,
+                started: now,
+            }
+        });
+        if ramp.target != target {
+            ramp.from = ramp.current;
+            ramp.target = target;
+            ramp.started = now;
+        }
+        let progress = (now.duration_since(ramp.started).as_secs_f32() / state.move_time).min(1.0);
+        for axis in 0..2 {
+            ramp.current[axis] =
+                ramp.from[axis] + (ramp.target[axis] as f32 - ramp.from[axis]) * progress;
+        }
+        write_position(&chans, dmx_chans, ramp.current);
+    }
+    // Fixtures released from the buffer follow their cues again
+    state.move_ramps.retain(|id, _| moved.contains(id));
+}
+
+/// Switches the serial agent between sending frames on its own (async) and sending
+/// one frame per `update_async` call (sync, the normal mode)
+fn set_continuous_output(state: &mut crate::ui::ConsoleState, continuous: bool) {
+    let Some(dmx) = &mut state.dmx_serial else {
+        return;
+    };
+    if continuous != dmx.is_sync() {
+        return;
+    }
+    if continuous {
+        dmx.set_async();
+        // The agent waits for a trigger in sync mode, this one starts its loop
+        if let Err(e) = dmx.update_async() {
+            state.dmx_connected = false;
+            state.dmx_serial_error = e.to_string();
+        }
+    } else {
+        dmx.set_sync();
+    }
+}
+
+/// Refreshes the connection state from the serial agent
+fn check_dmx_agent(state: &mut crate::ui::ConsoleState) {
     if let Some(dmx) = &mut state.dmx_serial {
         // Set the serial state
         match dmx.check_agent() {
-            Ok(()) => state.dmx_connected = true,
+            Ok(()) => {
+                state.dmx_connected = true;
+                state.dmx_failed_checks = 0;
+            }
             Err(e) => {
                 state.dmx_connected = false;
                 state.dmx_serial_error = e.to_string();
+                state.dmx_failed_checks = state.dmx_failed_checks.saturating_add(1);
             }
         }
     }
 }
+
+/// Tries to reopen the serial port of the DMX dongle, keeping the last sent values
+pub fn reconnect_dmx(state: &mut crate::ui::ConsoleState) {
+    if let Some(dmx) = &mut state.dmx_serial {
+        match dmx.reopen() {
+            Ok(()) => {
+                dmx.set_sync();
+                state.dmx_failed_checks = 0;
+                state.dmx_serial_error.clear();
+            }
+            Err(e) => {
+                state.dmx_serial_error = e.to_string();
+            }
+        }
+    }
+}
+
+/// Longest Oops lookback that can be configured, in seconds
+pub const MAX_OOPS_LOOKBACK: u32 = 60;
+
+/// Live look captured for the Oops button: only what shapes the output,
+/// never the patch or the cue data
+pub struct LookSnapshot {
+    taken_at: std::time::Instant,
+    fader_levels: Vec<f32>,
+    buffer: Vec<crate::dmx_types::DMXBufferValue>,
+    master_dimmer: f32,
+    submasters: Vec<f32>,
+}
+
+/// Records the current look once per second, keeping the last `MAX_OOPS_LOOKBACK` seconds
+pub fn record_look_snapshot(state: &mut crate::ui::ConsoleState) {
+    let now = std::time::Instant::now();
+    if state
+        .look_history
+        .back()
+        .is_some_and(|s| now.duration_since(s.taken_at).as_secs_f32() < 1.0)
+    {
+        return;
+    }
+    state.look_history.push_back(LookSnapshot {
+        taken_at: now,
+        fader_levels: state.executors.iter().map(|e| e.fader_level).collect(),
+        buffer: state.buffer.clone(),
+        master_dimmer: state.master_dimmer,
+        submasters: state.submasters.clone(),
+    });
+    while state.look_history.len() > MAX_OOPS_LOOKBACK as usize + 1 {
+        state.look_history.pop_front();
+    }
+}
+
+/// Restores the look from `oops_lookback` seconds ago.
+/// Later snapshots are dropped so pressing Oops again goes further back.
+pub fn oops(state: &mut crate::ui::ConsoleState) {
+    let lookback = std::time::Duration::from_secs(state.oops_lookback as u64);
+    let now = std::time::Instant::now();
+    let idx = state
+        .look_history
+        .iter()
+        .rposition(|s| now.duration_since(s.taken_at) >= lookback)
+        .unwrap_or(0);
+    state.look_history.truncate(idx + 1);
+    if let Some(snapshot) = state.look_history.pop_back() {
+        state
+            .executors
+            .iter_mut()
+            .zip(snapshot.fader_levels)
+            .for_each(|(exec, level)| exec.fader_level = level);
+        state.buffer = snapshot.buffer;
+        state.master_dimmer = snapshot.master_dimmer;
+        state.submasters = snapshot.submasters;
+    }
+}
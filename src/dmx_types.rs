@@ -15,6 +15,7 @@
 //!
 //! ## Playback
 //! - [`Cue`] - A snapshot of DMX values with timing information
+//! - [`CueNumber`] - Theatrical cue number, possibly decimal (2.5)
 //! - [`Executor`] - Playback controller with fader and cue list
 //! - [`DMXBufferValue`] - Single channel value for buffer manipulation
 //!
@@ -24,10 +25,15 @@
 //!
 //! ## Groups
 //! - [`FixtureGroup`] - Collective control of multiple fixtures
+//!
+//! ## Presets
+//! - [`Preset`] - Named color/position/beam values recalled onto fixtures
+//! - [`PresetKind`] - Which channel types a preset covers
 
 pub use open_dmx::DMX_CHANNELS;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 
 /// Color values for RGB-type fixtures.
 /// Represents the color channels commonly found in LED PARs and moving lights.
@@ -78,12 +84,62 @@ impl Color {
     pub fn has_color(&self) -> bool {
         self.r != 0 || self.g != 0 || self.b != 0 || self.w != 0
     }
+
+    /// Channel values rendering this color on a fixture with the `available` channel types.
+    /// With a White channel the white common to R, G and B moves to it, with an Amber
+    /// channel warm tones move to it. `w` and `amber` are added on top. Only the red,
+    /// green, blue, white and amber channels the fixture has are returned, so an RGB
+    /// fixture gets `r`, `g` and `b` unchanged.
+    pub fn to_fixture_channels(&self, available: &[ChannelType]) -> Vec<(ChannelType, u8)> {
+        let (mut r, mut g, mut b) = (self.r, self.g, self.b);
+        let (mut w, mut amber) = (self.w, self.amber);
+        if available.contains(&ChannelType::White) {
+            let white = r.min(g).min(b);
+            r -= white;
+            g -= white;
+            b -= white;
+            w = w.saturating_add(white);
+        }
+        if available.contains(&ChannelType::Amber) {
+            // Amber LEDs look like full red with half green
+            let warm = r.min(g.saturating_mul(2));
+            r -= warm;
+            g -= warm / 2;
+            amber = amber.saturating_add(warm);
+        }
+        [
+            (ChannelType::Red, r),
+            (ChannelType::Green, g),
+            (ChannelType::Blue, b),
+            (ChannelType::White, w),
+            (ChannelType::Amber, amber),
+        ]
+        .into_iter()
+        .filter(|(channel, _)| available.contains(channel))
+        .collect()
+    }
+
+    /// RGB look of these channel values on a fixture with the `available` channel types,
+    /// folding white and amber back in. The reverse of [`Color::to_fixture_channels`].
+    pub fn rendered_rgb(&self, available: &[ChannelType]) -> (u8, u8, u8) {
+        let (mut r, mut g, mut b) = (self.r, self.g, self.b);
+        if available.contains(&ChannelType::Amber) {
+            r = r.saturating_add(self.amber);
+            g = g.saturating_add(self.amber / 2);
+        }
+        if available.contains(&ChannelType::White) {
+            r = r.saturating_add(self.w);
+            g = g.saturating_add(self.w);
+            b = b.saturating_add(self.w);
+        }
+        (r, g, b)
+    }
 }
 
 /// Channel type definitions for fixtures.
 /// Represents the different types of DMX channels that a fixture can have.
 /// Each variant corresponds to a specific function or color in a lighting fixture.
-#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
 pub enum ChannelType {
     /// Intensity/Dimmer - controls overall brightness (0 = off, 255 = full)
     Intensity,
@@ -225,6 +281,10 @@ impl FixtureMode {
     pub fn total_channels(&self) -> usize {
         self.channels.len()
     }
+
+    pub fn has_channel(&self, channel_type: ChannelType) -> bool {
+        self.channels.iter().any(|c| c.channel_type == channel_type)
+    }
 }
 
 /// A fixture template defining channel layouts.
@@ -243,6 +303,9 @@ pub struct FixtureTemplate {
     pub modes: Vec<FixtureMode>,
     /// Whether this template was created by the user (true) or is built-in (false)
     pub is_user_defined: bool,
+    /// DMX ranges of the shutter/strobe channel, linear 1-255 when not set
+    #[serde(default)]
+    pub shutter_map: Option<ShutterMap>,
 }
 
 impl FixtureTemplate {
@@ -253,6 +316,7 @@ impl FixtureTemplate {
             manufacturer: manufacturer.to_string(),
             modes: Vec::new(),
             is_user_defined: false,
+            shutter_map: None,
         }
     }
 
@@ -267,6 +331,82 @@ impl FixtureTemplate {
     pub fn default_mode(&self) -> Option<&FixtureMode> {
         self.modes.first()
     }
+
+    pub fn shutter_map(&self) -> ShutterMap {
+        self.shutter_map.unwrap_or_default()
+    }
+}
+
+/// Where a fixture puts open, strobe and closed on its shutter/strobe channel.
+/// Strobe frequencies between `min_hz` and `max_hz` map linearly onto
+/// `strobe_start..=strobe_end`, which may run downwards.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ShutterMap {
+    /// DMX value with the shutter open, no strobe
+    pub open: u8,
+    /// DMX value of the slowest strobe, at `min_hz`
+    pub strobe_start: u8,
+    /// DMX value of the fastest strobe, at `max_hz`
+    pub strobe_end: u8,
+    /// Slowest strobe frequency of the fixture
+    pub min_hz: f32,
+    /// Fastest strobe frequency of the fixture
+    pub max_hz: f32,
+    /// DMX value with the shutter closed
+    pub closed: u8,
+}
+
+impl Default for ShutterMap {
+    /// The plain "0 = open, 1-255 = strobe speed" channel
+    fn default() -> Self {
+        Self {
+            open: 0,
+            strobe_start: 1,
+            strobe_end: u8::MAX,
+            min_hz: 1.0,
+            max_hz: 20.0,
+            closed: 0,
+        }
+    }
+}
+
+impl ShutterMap {
+    /// Whether the shutter can close: the default map has no closed value apart from open
+    pub fn can_close(&self) -> bool {
+        self.closed != self.open
+    }
+
+    /// DMX value strobing at `hz`, clamped to the fixture's range. 0 Hz opens the shutter.
+    pub fn strobe_value(&self, hz: f32) -> u8 {
+        if hz <= 0.0 {
+            return self.open;
+        }
+        let t = if self.max_hz > self.min_hz {
+            ((hz - self.min_hz) / (self.max_hz - self.min_hz)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (start, end) = (self.strobe_start as f32, self.strobe_end as f32);
+        (start + t * (end - start)).round() as u8
+    }
+
+    /// Strobe frequency of a DMX value, 0 outside the strobe range
+    pub fn strobe_hz(&self, value: u8) -> f32 {
+        let (low, high) = if self.strobe_start <= self.strobe_end {
+            (self.strobe_start, self.strobe_end)
+        } else {
+            (self.strobe_end, self.strobe_start)
+        };
+        if !(low..=high).contains(&value) {
+            return 0.0;
+        }
+        if self.strobe_start == self.strobe_end {
+            return self.min_hz;
+        }
+        let t = (value as f32 - self.strobe_start as f32)
+            / (self.strobe_end as f32 - self.strobe_start as f32);
+        self.min_hz + t * (self.max_hz - self.min_hz)
+    }
 }
 
 /// Library of fixture templates (predefined + user-defined).
@@ -632,8 +772,23 @@ pub struct Fixture {
     pub zoom: u8,
     /// Current focus position
     pub focus: u8,
+    /// Lowest value ever output on the intensity channel
+    #[serde(default)]
+    pub intensity_min: u8,
+    /// Highest value ever output on the intensity channel, to protect lamps
+    #[serde(default = "full_intensity")]
+    pub intensity_max: u8,
+    /// Target color temperature in Kelvin, driving the CTO/CTB channels
+    #[serde(default = "neutral_cct")]
+    pub cct: u16,
     /// Custom channel values for undefined channel types (channel_offset -> value)
     pub custom_values: HashMap<usize, u8>,
+    /// Free text for the operator, no effect on output
+    #[serde(default)]
+    pub notes: String,
+    /// A disabled fixture outputs 0 on all its channels, which stay patched to it
+    #[serde(default = "fixture_enabled")]
+    pub enabled: bool,
 }
 
 impl Fixture {
@@ -657,18 +812,39 @@ impl Fixture {
             gobo: Default::default(),
             zoom: 128,
             focus: 128,
+            intensity_min: 0,
+            intensity_max: u8::MAX,
+            cct: CCT_NEUTRAL,
             custom_values: HashMap::new(),
             intensity: Default::default(),
+            notes: String::new(),
+            enabled: true,
         }
     }
 
+    /// Bounds an intensity value to the fixture's min/max limits
+    pub fn limit_intensity(&self, value: u8) -> u8 {
+        value.clamp(
+            self.intensity_min,
+            self.intensity_max.max(self.intensity_min),
+        )
+    }
+
+    /// Whether the limits can change an intensity value at all
+    pub fn has_intensity_limits(&self) -> bool {
+        self.intensity_min > 0 || self.intensity_max < u8::MAX
+    }
+
     pub fn get_dmx_values(&self, template: &FixtureTemplate) -> Vec<u8> {
         if let Some(mode) = template.get_mode(self.mode_index) {
             let mut values = vec![0u8; mode.total_channels()];
+            if !self.enabled {
+                return values;
+            }
 
             for channel in &mode.channels {
                 let value = match channel.channel_type {
-                    ChannelType::Intensity => self.intensity,
+                    ChannelType::Intensity => self.limit_intensity(self.intensity),
                     ChannelType::Red => self.color.r,
                     ChannelType::Green => self.color.g,
                     ChannelType::Blue => self.color.b,
@@ -704,7 +880,7 @@ impl Fixture {
 
             for chan_def in &mode.channels {
                 let value = match chan_def.channel_type {
-                    ChannelType::Intensity => self.intensity,
+                    ChannelType::Intensity => self.limit_intensity(self.intensity),
                     ChannelType::Red => self.color.r,
                     ChannelType::Green => self.color.g,
                     ChannelType::Blue => self.color.b,
@@ -734,6 +910,64 @@ impl Fixture {
             Vec::new()
         }
     }
+
+    /// Sets the runtime value behind a channel of this fixture's mode
+    pub fn set_channel_value(&mut self, chan_def: &ChannelDef, value: u8) {
+        match chan_def.channel_type {
+            ChannelType::Intensity => self.intensity = value,
+            ChannelType::Red => self.color.r = value,
+            ChannelType::Green => self.color.g = value,
+            ChannelType::Blue => self.color.b = value,
+            ChannelType::White => self.color.w = value,
+            ChannelType::Amber => self.color.amber = value,
+            ChannelType::UV => self.color.uv = value,
+            ChannelType::Pan => self.pan = (self.pan & 0x00FF) | ((value as u16) << 8),
+            ChannelType::PanFine => self.pan = (self.pan & 0xFF00) | value as u16,
+            ChannelType::Tilt => self.tilt = (self.tilt & 0x00FF) | ((value as u16) << 8),
+            ChannelType::TiltFine => self.tilt = (self.tilt & 0xFF00) | value as u16,
+            ChannelType::Shutter | ChannelType::Strobe => self.shutter = value,
+            ChannelType::GoboWheel => self.gobo = value,
+            ChannelType::Zoom => self.zoom = value,
+            ChannelType::Focus => self.focus = value,
+            _ => {
+                self.custom_values.insert(chan_def.offset as usize, value);
+            }
+        }
+    }
+}
+
+/// Native color temperature of a fixture with both CTO and CTB at 0
+pub const CCT_NEUTRAL: u16 = 5600;
+/// Color temperature reached with CTO at full
+pub const CCT_WARMEST: u16 = 2700;
+/// Color temperature reached with CTB at full
+pub const CCT_COOLEST: u16 = 10000;
+
+fn full_intensity() -> u8 {
+    u8::MAX
+}
+
+fn neutral_cct() -> u16 {
+    CCT_NEUTRAL
+}
+
+fn fixture_enabled() -> bool {
+    true
+}
+
+/// Maps a color temperature to (CTO, CTB) channel values.
+/// Interpolates in mireds, the scale correction filters are linear in.
+pub fn cct_to_cto_ctb(kelvin: u16) -> (u8, u8) {
+    let mired = |k: u16| 1_000_000.0 / k as f32;
+    let target = mired(kelvin.clamp(CCT_WARMEST, CCT_COOLEST));
+    let neutral = mired(CCT_NEUTRAL);
+    if target >= neutral {
+        let amount = (target - neutral) / (mired(CCT_WARMEST) - neutral);
+        ((amount * 255.0).round() as u8, 0)
+    } else {
+        let amount = (neutral - target) / (neutral - mired(CCT_COOLEST));
+        (0, (amount * 255.0).round() as u8)
+    }
 }
 
 /// Fixture group for collective control.
@@ -762,6 +996,130 @@ impl FixtureGroup {
     }
 }
 
+/// Category of a preset, deciding which channel types it records and recalls
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum PresetKind {
+    /// Color mixing, color wheel and color temperature channels
+    Color,
+    /// Pan and tilt channels
+    Position,
+    /// Gobo, shutter, zoom, focus, prism and frost channels
+    Beam,
+}
+
+impl PresetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PresetKind::Color => "Color",
+            PresetKind::Position => "Position",
+            PresetKind::Beam => "Beam",
+        }
+    }
+
+    /// Whether a preset of this kind touches the given channel type
+    pub fn includes(&self, channel_type: ChannelType) -> bool {
+        match self {
+            PresetKind::Color => matches!(
+                channel_type,
+                ChannelType::Red
+                    | ChannelType::Green
+                    | ChannelType::Blue
+                    | ChannelType::White
+                    | ChannelType::Amber
+                    | ChannelType::UV
+                    | ChannelType::ColorWheel
+                    | ChannelType::CTO
+                    | ChannelType::CTB
+            ),
+            PresetKind::Position => matches!(
+                channel_type,
+                ChannelType::Pan | ChannelType::PanFine | ChannelType::Tilt | ChannelType::TiltFine
+            ),
+            PresetKind::Beam => matches!(
+                channel_type,
+                ChannelType::GoboWheel
+                    | ChannelType::GoboRotation
+                    | ChannelType::GoboWheel2
+                    | ChannelType::GoboRotation2
+                    | ChannelType::Shutter
+                    | ChannelType::Strobe
+                    | ChannelType::Zoom
+                    | ChannelType::Focus
+                    | ChannelType::Prism
+                    | ChannelType::Frost
+            ),
+        }
+    }
+}
+
+/// Quick selection of the fixtures whose mode has a kind of channel
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FixtureFilter {
+    /// Has an intensity channel
+    Dimmer,
+    /// Has red, green and blue channels
+    Rgb,
+    /// Has any color channel, see [`PresetKind::Color`]
+    Color,
+    /// Has a pan channel
+    Pan,
+    /// Has a tilt channel
+    Tilt,
+    /// Has any beam channel, see [`PresetKind::Beam`]
+    Beam,
+}
+
+impl FixtureFilter {
+    pub const ALL: [FixtureFilter; 6] = [
+        FixtureFilter::Dimmer,
+        FixtureFilter::Rgb,
+        FixtureFilter::Color,
+        FixtureFilter::Pan,
+        FixtureFilter::Tilt,
+        FixtureFilter::Beam,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FixtureFilter::Dimmer => "Dimmer",
+            FixtureFilter::Rgb => "RGB",
+            FixtureFilter::Color => "Any Color",
+            FixtureFilter::Pan => "Pan",
+            FixtureFilter::Tilt => "Tilt",
+            FixtureFilter::Beam => "Beam",
+        }
+    }
+
+    pub fn matches(&self, mode: &FixtureMode) -> bool {
+        let any = |kind: PresetKind| mode.channels.iter().any(|c| kind.includes(c.channel_type));
+        match self {
+            FixtureFilter::Dimmer => mode.has_channel(ChannelType::Intensity),
+            FixtureFilter::Rgb => [ChannelType::Red, ChannelType::Green, ChannelType::Blue]
+                .into_iter()
+                .all(|c| mode.has_channel(c)),
+            FixtureFilter::Color => any(PresetKind::Color),
+            FixtureFilter::Pan => mode.has_channel(ChannelType::Pan),
+            FixtureFilter::Tilt => mode.has_channel(ChannelType::Tilt),
+            FixtureFilter::Beam => any(PresetKind::Beam),
+        }
+    }
+}
+
+/// Named set of channel values that can be recalled onto any fixture selection.
+/// Only channel types matching the preset's kind are stored, so recalling a
+/// color preset leaves position and intensity untouched.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Preset {
+    /// Unique identifier for this preset
+    pub id: u32,
+    /// Which family of channels this preset covers
+    pub kind: PresetKind,
+    /// User-assigned name for this preset
+    pub name: String,
+    /// Value recalled on every channel of the given type
+    pub values: HashMap<ChannelType, u8>,
+}
+
 /// Audio playback action for show control.
 /// Defines how audio tracks behave in relation to the show/sequence.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -799,6 +1157,12 @@ pub struct AudioTrack {
     pub duration: f32,
     /// Audio action behavior
     pub action: AudioAction,
+    /// Gain trim in dB applied on top of the volume (-12.0 to +6.0)
+    #[serde(default)]
+    pub trim_db: f32,
+    /// The audio file could not be found the last time it was checked
+    #[serde(skip)]
+    pub missing: bool,
 }
 
 impl AudioTrack {
@@ -814,17 +1178,201 @@ impl AudioTrack {
             volume: 1.0,
             duration: 0.0,
             action: AudioAction::None,
+            trim_db: 0.0,
+            missing: false,
+        }
+    }
+
+    /// Re-checks that the audio file still exists, updating `missing`
+    pub fn check_file(&mut self) -> bool {
+        self.missing = !std::path::Path::new(&self.file_path).exists();
+        !self.missing
+    }
+
+    /// Position playback stops at, within the track
+    pub fn end(&self) -> f32 {
+        self.end_point
+            .unwrap_or(self.duration)
+            .clamp(0.0, self.duration.max(0.0))
+    }
+
+    /// Valid fade in times: the fades have to fit between the start and end points
+    pub fn fade_in_range(&self) -> RangeInclusive<f32> {
+        0.0..=(self.end() - self.start_point - self.fade_out).max(0.0)
+    }
+
+    /// Valid fade out times: the fades have to fit between the start and end points
+    pub fn fade_out_range(&self) -> RangeInclusive<f32> {
+        0.0..=(self.end() - self.start_point - self.fade_in).max(0.0)
+    }
+
+    /// Valid start points, leaving room for both fades before the end point
+    pub fn start_range(&self) -> RangeInclusive<f32> {
+        0.0..=(self.end() - self.fade_in - self.fade_out).max(0.0)
+    }
+
+    /// Valid end points, leaving room for both fades after the start point
+    pub fn end_range(&self) -> RangeInclusive<f32> {
+        let duration = self.duration.max(0.0);
+        (self.start_point + self.fade_in + self.fade_out).min(duration)..=duration
+    }
+
+    /// Brings start, end and fades back within their ranges, the start point
+    /// first, then the end point, then the fades
+    pub fn clamp_edit_points(&mut self) {
+        let duration = self.duration.max(0.0);
+        self.start_point = self.start_point.clamp(0.0, duration);
+        if let Some(end) = self.end_point {
+            let end = end.clamp(self.start_point, duration);
+            self.end_point = (end > 0.0 && end < duration).then_some(end);
+        }
+        self.fade_in = self
+            .fade_in
+            .clamp(0.0, (self.end() - self.start_point).max(0.0));
+        self.fade_out = self.fade_out.clamp(0.0, *self.fade_out_range().end());
+    }
+}
+
+/// Easing of a cue fade, mapping linear progress (0.0 to 1.0) to output progress
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub enum FadeCurve {
+    #[default]
+    Linear,
+    /// Slow start and end
+    SineInOut,
+    /// Slow start, fast end
+    ExpIn,
+    /// Fast start, slow end
+    ExpOut,
+}
+
+impl FadeCurve {
+    pub const ALL: [FadeCurve; 4] = [
+        FadeCurve::Linear,
+        FadeCurve::SineInOut,
+        FadeCurve::ExpIn,
+        FadeCurve::ExpOut,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FadeCurve::Linear => "Linear",
+            FadeCurve::SineInOut => "Sine In/Out",
+            FadeCurve::ExpIn => "Exp In",
+            FadeCurve::ExpOut => "Exp Out",
+        }
+    }
+
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            FadeCurve::Linear => t,
+            FadeCurve::SineInOut => -((std::f32::consts::PI * t).cos() - 1.0) / 2.0,
+            FadeCurve::ExpIn if t == 0.0 => 0.0,
+            FadeCurve::ExpIn => 2f32.powf(10.0 * t - 10.0),
+            FadeCurve::ExpOut if t == 1.0 => 1.0,
+            FadeCurve::ExpOut => 1.0 - 2f32.powf(-10.0 * t),
+        }
+    }
+}
+
+/// Cue number as theatrical consoles use them: whole numbers, with decimal cues
+/// (2.5, 2.75) inserted in between. Kept in thousandths so it sorts and compares exactly.
+/// Serialized as a plain number, so integer ids from older show files still load.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct CueNumber(u32);
+
+impl CueNumber {
+    /// Decimal places a cue number can have
+    pub const DECIMALS: usize = 3;
+    const SCALE: u32 = 1000;
+    /// Largest whole part, so the next whole number always fits as well
+    const MAX_WHOLE: u32 = u32::MAX / Self::SCALE - 1;
+    /// Number of the first cue in a list
+    pub const FIRST: Self = Self(Self::SCALE);
+
+    /// `number` as a cue number, `None` past the largest one
+    pub const fn whole(number: u32) -> Option<Self> {
+        if number > Self::MAX_WHOLE {
+            return None;
+        }
+        Some(Self(number * Self::SCALE))
+    }
+
+    pub fn from_f64(number: f64) -> Result<Self, String> {
+        let max = (Self::MAX_WHOLE + 1) as f64;
+        if !(0.0..max).contains(&number) {
+            return Err(format!("Invalid cue number: {number}"));
+        }
+        Ok(Self((number * Self::SCALE as f64).round() as u32))
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+
+    /// First whole number after this one, where a new cue is stored by default
+    pub fn next_whole(&self) -> Option<Self> {
+        Self::whole(self.0 / Self::SCALE + 1)
+    }
+}
+
+impl std::fmt::Display for CueNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (whole, fraction) = (self.0 / Self::SCALE, self.0 % Self::SCALE);
+        if fraction == 0 {
+            write!(f, "{whole}")
+        } else {
+            let fraction = format!("{fraction:03}");
+            write!(f, "{whole}.{}", fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+impl std::str::FromStr for CueNumber {
+    type Err = String;
+
+    /// Parses "3", "2.5" or ".5", with at most [`CueNumber::DECIMALS`] decimals
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid cue number: {s}");
+        let (whole, fraction) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+        if fraction.len() > Self::DECIMALS || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let whole: u32 = match whole {
+            "" if !fraction.is_empty() => 0,
+            whole => whole.parse().map_err(|_| invalid())?,
+        };
+        let fraction: u32 = format!("{fraction:0<3}").parse().map_err(|_| invalid())?;
+        Self::whole(whole)
+            .map(|number| Self(number.0 + fraction))
+            .ok_or_else(invalid)
+    }
+}
+
+impl Serialize for CueNumber {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_multiple_of(Self::SCALE) {
+            serializer.serialize_u32(self.0 / Self::SCALE)
+        } else {
+            serializer.serialize_f64(self.as_f64())
         }
     }
 }
 
+impl<'de> Deserialize<'de> for CueNumber {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_f64(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Represents a cue containing DMX values and timing information.
 /// A cue is a snapshot of all DMX channel values that can be recalled
 /// and played back through an executor. Supports fade times for smooth transitions.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Cue {
-    /// Unique identifier for the cue
-    pub id: u32,
+    /// Cue number, unique within its executor. Cue lists are kept sorted by it.
+    pub id: CueNumber,
     /// Human-readable name of the cue
     pub name: String,
     /// Fade time in seconds (how long to transition to this cue)
@@ -833,20 +1381,50 @@ pub struct Cue {
     pub delay: f32,
     /// DMX channel values (512 channels, index 0 = channel 1)
     pub levels: Vec<u8>,
+    /// Seconds to wait after the fade completes before automatically going
+    /// to the next cue. `None` keeps the cue on manual GO.
+    #[serde(default)]
+    pub follow: Option<f32>,
+    /// Easing applied to the fade into this cue
+    #[serde(default)]
+    pub fade_curve: FadeCurve,
+    /// Skipped by GO and GO BACK, the cue stays in the list
+    #[serde(default)]
+    pub disabled: bool,
+    /// Free text for the operator ("warm wash for act 2"), no effect on output
+    #[serde(default)]
+    pub notes: String,
 }
 
 impl Cue {
-    pub fn new(id: u32) -> Self {
+    pub fn new(id: CueNumber) -> Self {
         Self {
             id,
             name: format!("Cue {}", id),
             fade_time: 0.0,
             delay: 0.0,
             levels: vec![0; DMX_CHANNELS],
+            follow: None,
+            fade_curve: FadeCurve::Linear,
+            disabled: false,
+            notes: String::new(),
         }
     }
 }
 
+/// Cues of an executor that run on their own once the first one is triggered
+#[derive(Clone, Default, Debug)]
+pub struct FollowChain {
+    /// Indices in the cue list, in playback order
+    pub cues: Vec<usize>,
+    /// Seconds from the first cue's GO to each cue's GO
+    pub starts: Vec<f32>,
+    /// Total of the fades and follow times of the chain, cue delays are not played back
+    pub runtime: f32,
+    /// The last cue follows back into the first one
+    pub loops: bool,
+}
+
 /// Represents a single DMX channel value in the buffer.
 /// Used for the temporary buffer that holds values before storing to a cue,
 /// or for direct channel manipulation commands.
@@ -878,33 +1456,117 @@ pub enum FadeDirection {
 /// - A fader for intensity control
 /// - GO/BACK buttons for cue advancement
 /// - Fade engine for smooth transitions between cues
+///
+/// Only the programming (cues, lock) is serialized, the playback state
+/// always starts from scratch.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Executor {
     /// Index of this executor (0-based)
     pub id: u32,
-    /// Currently active cue ID (if any)
-    pub current_cue: Option<u32>,
+    /// Currently active cue number (if any)
+    pub current_cue: Option<CueNumber>,
     /// Index of the current cue in the cue_list
     pub current_cue_index: usize,
     /// Whether the executor is currently playing (not currently used)
+    #[serde(skip)]
     pub is_running: bool,
     /// List of cues stored in this executor
     pub cue_list: Vec<Cue>,
     /// Fader position (0.0 to 1.0) - controls output intensity
+    #[serde(skip)]
     pub fader_level: f32,
     /// DMX values from the current cue (cached for mixing)
+    #[serde(skip, default = "empty_levels")]
     pub stored_channels: Vec<u8>,
     /// Target fader level for fade transitions
+    #[serde(skip)]
     pub target_level: f32,
     /// Current output level (used during fade interpolation)
+    #[serde(skip)]
     pub current_output_level: f32,
     /// Timestamp when fade started (for interpolation)
+    #[serde(skip)]
     pub fade_start_time: f64,
     /// Whether a fade is currently in progress
+    #[serde(skip)]
     pub is_fading: bool,
+    /// Progress of the current fade (0.0 to 1.0)
+    #[serde(skip)]
+    pub fade_progress: f32,
     /// Last fader level (for detecting fader movements)
+    #[serde(skip)]
     pub last_fader_level: f32,
     /// Direction of last cue change (for interpolation)
+    #[serde(skip)]
     pub last_direction: Option<FadeDirection>,
+    /// Index of the cue the current fade started from
+    #[serde(skip)]
+    pub fade_from_index: usize,
+    /// Last value received on the OSC GO address, for rising-edge detection
+    #[serde(skip)]
+    pub osc_go_level: f32,
+    /// Last value received on the OSC GO BACK address, for rising-edge detection
+    #[serde(skip)]
+    pub osc_go_back_level: f32,
+    /// Protects the executor from Store/Edit/Delete/Copy/Move, GO still works
+    #[serde(default)]
+    pub locked: bool,
+    /// Index of the submaster scaling this executor's output (0-based), if any
+    #[serde(default)]
+    pub submaster: Option<u8>,
+    /// Response of the fader, applied before it scales the cue output
+    #[serde(default)]
+    pub dimmer_curve: DimmerCurve,
+    /// GO past the last cue starts over at the first, otherwise it stops there
+    #[serde(default = "executor_wraps")]
+    pub wrap: bool,
+    /// Timestamp of the last GO refused at either end of the cue list, when not wrapping
+    #[serde(skip)]
+    pub end_of_list_time: Option<f64>,
+}
+
+fn executor_wraps() -> bool {
+    true
+}
+
+/// Maps a fader position (0.0 to 1.0) to an output level
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub enum DimmerCurve {
+    #[default]
+    Linear,
+    /// Square law, finer control at the low end for LED fixtures
+    Square,
+    /// Smoothstep, gentle at both ends
+    SCurve,
+}
+
+impl DimmerCurve {
+    pub const ALL: [DimmerCurve; 3] = [
+        DimmerCurve::Linear,
+        DimmerCurve::Square,
+        DimmerCurve::SCurve,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DimmerCurve::Linear => "Linear",
+            DimmerCurve::Square => "Square",
+            DimmerCurve::SCurve => "S-Curve",
+        }
+    }
+
+    pub fn apply(&self, level: f32) -> f32 {
+        let level = level.clamp(0.0, 1.0);
+        match self {
+            DimmerCurve::Linear => level,
+            DimmerCurve::Square => level * level,
+            DimmerCurve::SCurve => level * level * (3.0 - 2.0 * level),
+        }
+    }
+}
+
+fn empty_levels() -> Vec<u8> {
+    vec![0; DMX_CHANNELS]
 }
 
 impl Executor {
@@ -921,38 +1583,194 @@ impl Executor {
             current_output_level: Default::default(),
             fade_start_time: Default::default(),
             is_fading: Default::default(),
+            fade_progress: Default::default(),
             last_fader_level: Default::default(),
             last_direction: None,
+            fade_from_index: 0,
+            osc_go_level: 0.0,
+            osc_go_back_level: 0.0,
+            locked: false,
+            submaster: None,
+            dimmer_curve: DimmerCurve::Linear,
+            wrap: true,
+            end_of_list_time: None,
         }
     }
 
-    pub fn go(&mut self) {
-        if self.cue_list.is_empty() {
-            return;
+    /// Number a new cue gets by default: the next whole number after the last cue.
+    /// Fails when the last cue already has the largest number.
+    pub fn next_cue_number(&self) -> Result<CueNumber, String> {
+        match self.cue_list.last() {
+            None => Ok(CueNumber::FIRST),
+            Some(cue) => cue
+                .id
+                .next_whole()
+                .ok_or_else(|| format!("No cue number left after cue {}", cue.id)),
         }
-        self.current_cue_index = self.current_cue_index.saturating_add(1) % self.cue_list.len();
-        self.current_cue = Some(self.cue_list[self.current_cue_index].id);
-        self.stored_channels = self.cue_list[self.current_cue_index].levels.clone();
-        self.target_level = self.fader_level;
-        self.is_fading = true;
-        self.last_direction = Some(FadeDirection::Positive);
-        self.fade_start_time = std::time::SystemTime::now()
+    }
+
+    /// Index of the cue numbered `number`
+    pub fn cue_index(&self, number: CueNumber) -> Option<usize> {
+        self.cue_list.iter().position(|cue| cue.id == number)
+    }
+
+    /// Adds a cue at its place in the numbering. Fails if the number is taken.
+    pub fn insert_cue(&mut self, cue: Cue) -> Result<(), String> {
+        match self.cue_list.binary_search_by_key(&cue.id, |c| c.id) {
+            Ok(_) => Err(format!(
+                "Cue {} already exists in executor {}",
+                cue.id,
+                self.id + 1
+            )),
+            Err(idx) => {
+                if idx <= self.current_cue_index && !self.cue_list.is_empty() {
+                    self.current_cue_index += 1;
+                }
+                self.cue_list.insert(idx, cue);
+                Ok(())
+            }
+        }
+    }
+
+    /// Takes a cue out of the list, the current cue stays current when possible
+    pub fn remove_cue(&mut self, idx: usize) -> Cue {
+        let cue = self.cue_list.remove(idx);
+        if idx < self.current_cue_index {
+            self.current_cue_index -= 1;
+        }
+        self.current_cue_index = self
+            .current_cue_index
+            .min(self.cue_list.len().saturating_sub(1));
+        cue
+    }
+
+    /// Restores the cue order after renumbering, the current cue stays current
+    pub fn sort_cues(&mut self) {
+        let current = self.cue_list.get(self.current_cue_index).map(|c| c.id);
+        self.cue_list.sort_by_key(|c| c.id);
+        if let Some(idx) = current.and_then(|id| self.cue_index(id)) {
+            self.current_cue_index = idx;
+        }
+    }
+
+    /// Index of the next enabled cue in `direction`, wrapping around the list if
+    /// `wrap` is set. `None` when every cue is disabled or the end is reached.
+    fn next_enabled_cue(&self, direction: FadeDirection) -> Option<usize> {
+        let len = self.cue_list.len();
+        let current = self.current_cue_index;
+        (1..=len)
+            .filter_map(|step| match direction {
+                FadeDirection::Positive if self.wrap => Some((current + step) % len),
+                FadeDirection::Negative if self.wrap => Some((current + len - step) % len),
+                FadeDirection::Positive => Some(current + step).filter(|&idx| idx < len),
+                FadeDirection::Negative => current.checked_sub(step),
+            })
+            .find(|&idx| !self.cue_list[idx].disabled)
+    }
+
+    /// Whether a GO was refused at the end of the list less than `seconds` ago
+    pub fn hit_end_of_list(&self, seconds: f64) -> bool {
+        let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs_f64();
+        self.end_of_list_time
+            .is_some_and(|time| now - time < seconds)
+    }
+
+    /// Sequences of enabled cues linked by follow times, in cue list order.
+    /// A cue without a follow time ends its chain, lone manual cues are left out.
+    /// Chains only run past the last cue into the first when the executor wraps.
+    pub fn follow_chains(&self) -> Vec<FollowChain> {
+        let enabled: Vec<usize> = (0..self.cue_list.len())
+            .filter(|&idx| !self.cue_list[idx].disabled)
+            .collect();
+        let follows = |pos: usize| self.cue_list[enabled[pos]].follow.is_some();
+        let len = enabled.len();
+        // A chain starts where the previous enabled cue does not follow, the previous
+        // cue of the first being the last one only when the executor wraps
+        let mut heads: Vec<usize> = (0..len)
+            .filter(|&pos| (pos == 0 && !self.wrap) || !follows((pos + len - 1) % len))
+            .collect();
+        if heads.is_empty() && len > 0 {
+            heads.push(0);
+        }
+
+        let mut chains = Vec::new();
+        for head in heads {
+            let mut chain = FollowChain::default();
+            let mut pos = head;
+            loop {
+                let cue = &self.cue_list[enabled[pos]];
+                chain.cues.push(enabled[pos]);
+                chain.starts.push(chain.runtime);
+                chain.runtime += cue.fade_time.max(0.0);
+                let Some(follow) = cue.follow else {
+                    break;
+                };
+                if pos + 1 == len && !self.wrap {
+                    break;
+                }
+                chain.runtime += follow.max(0.0);
+                pos = (pos + 1) % len;
+                if pos == head {
+                    chain.loops = true;
+                    break;
+                }
+            }
+            if chain.cues.len() > 1 || chain.loops {
+                chains.push(chain);
+            }
+        }
+        chains
+    }
+
+    pub fn go(&mut self) {
+        self.step(FadeDirection::Positive);
     }
 
     pub fn go_back(&mut self) {
-        if self.cue_list.is_empty() {
+        self.step(FadeDirection::Negative);
+    }
+
+    /// Fades directly into the cue at `idx`, skipping the cues in between
+    pub fn go_to(&mut self, idx: usize) {
+        if idx >= self.cue_list.len() {
             return;
         }
-        self.current_cue_index =
-            (self.cue_list.len() + self.current_cue_index - 1) % self.cue_list.len();
+        let direction = if idx < self.current_cue_index {
+            FadeDirection::Negative
+        } else {
+            FadeDirection::Positive
+        };
+        self.start_fade(idx, direction);
+    }
+
+    /// Fades to the next enabled cue in `direction`
+    fn step(&mut self, direction: FadeDirection) {
+        let Some(next_idx) = self.next_enabled_cue(direction) else {
+            if !self.wrap && !self.cue_list.is_empty() {
+                self.end_of_list_time = Some(
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs_f64(),
+                );
+            }
+            return;
+        };
+        self.start_fade(next_idx, direction);
+    }
+
+    fn start_fade(&mut self, next_idx: usize, direction: FadeDirection) {
+        self.fade_from_index = self.current_cue_index;
+        self.current_cue_index = next_idx;
         self.current_cue = Some(self.cue_list[self.current_cue_index].id);
         self.stored_channels = self.cue_list[self.current_cue_index].levels.clone();
         self.target_level = self.fader_level;
         self.is_fading = true;
-        self.last_direction = Some(FadeDirection::Negative);
+        self.fade_progress = 0.0;
+        self.last_direction = Some(direction);
         self.fade_start_time = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -963,22 +1781,28 @@ impl Executor {
         if self.last_fader_level == 0.0 && self.fader_level != 0.0 {
             self.target_level = 1.0;
             self.is_fading = true;
+            self.fade_progress = 0.0;
             self.fade_start_time = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs_f64();
         }
         self.last_fader_level = self.fader_level;
+        // Output level the fader stands for once its dimmer curve is applied
+        let fader_output = self.dimmer_curve.apply(self.fader_level);
         if !self.is_fading || self.cue_list.is_empty() {
-            self.current_output_level = self.fader_level;
+            self.current_output_level = fader_output;
+            self.fade_progress = 1.0;
             return;
         }
 
         let current_cue = &self.cue_list[self.current_cue_index];
         let fade_time = current_cue.fade_time;
+        let fade_curve = current_cue.fade_curve;
         if fade_time <= 0.0 {
-            self.current_output_level = self.fader_level;
+            self.current_output_level = fader_output;
             self.is_fading = false;
+            self.fade_progress = 1.0;
             return;
         }
 
@@ -990,11 +1814,41 @@ impl Executor {
         let elapsed = now - self.fade_start_time;
         let progress = (elapsed / fade_time as f64).min(1.0) as f32;
 
-        self.current_output_level = progress * self.fader_level;
+        self.fade_progress = progress;
+        // Also drives the crossfade between cues in mix_executor_outputs
+        self.current_output_level = fade_curve.apply(progress) * fader_output;
 
         if progress >= 1.0 {
             self.is_fading = false;
-            self.current_output_level = self.fader_level;
+            self.current_output_level = fader_output;
+        }
+    }
+
+    /// Goes to the next cue once the current cue's follow time has elapsed
+    /// after its fade. Only runs while the executor is up.
+    pub fn update_follow(&mut self) {
+        if self.is_fading || self.fader_level <= 0.0 {
+            return;
+        }
+        // Nothing to follow into, the end of a list that doesn't wrap
+        if self.next_enabled_cue(FadeDirection::Positive).is_none() {
+            return;
+        }
+        let Some(current_cue) = self.cue_list.get(self.current_cue_index) else {
+            return;
+        };
+        let Some(follow) = current_cue.follow else {
+            return;
+        };
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs_f64();
+
+        let fade_end = self.fade_start_time + current_cue.fade_time.max(0.0) as f64;
+        if now - fade_end >= follow as f64 {
+            self.go();
         }
     }
 }
@@ -1005,3 +1859,260 @@ pub use ChannelType as Ch;
 pub fn ch(channel_type: ChannelType, offset: u8) -> ChannelDef {
     ChannelDef::new(channel_type, offset)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Executor with cues 1 to `count`, sitting on the cue at `current`
+    fn executor(count: u32, current: usize, wrap: bool) -> Executor {
+        let mut exec = Executor::new(0);
+        exec.cue_list = (1..=count)
+            .map(|id| Cue::new(CueNumber::whole(id).unwrap()))
+            .collect();
+        exec.current_cue_index = current;
+        exec.wrap = wrap;
+        exec
+    }
+
+    #[test]
+    fn go_at_last_cue_wraps_to_first() {
+        let mut exec = executor(3, 2, true);
+        exec.go();
+        assert_eq!(exec.current_cue_index, 0);
+        assert_eq!(exec.end_of_list_time, None);
+    }
+
+    #[test]
+    fn go_back_at_first_cue_wraps_to_last() {
+        let mut exec = executor(3, 0, true);
+        exec.go_back();
+        assert_eq!(exec.current_cue_index, 2);
+        assert_eq!(exec.end_of_list_time, None);
+    }
+
+    #[test]
+    fn go_at_last_cue_stops_without_wrap() {
+        let mut exec = executor(3, 2, false);
+        exec.go();
+        assert_eq!(exec.current_cue_index, 2);
+        assert!(!exec.is_fading);
+        assert!(exec.hit_end_of_list(1.0));
+    }
+
+    #[test]
+    fn go_back_at_first_cue_stops_without_wrap() {
+        let mut exec = executor(3, 0, false);
+        exec.go_back();
+        assert_eq!(exec.current_cue_index, 0);
+        assert!(!exec.is_fading);
+        assert!(exec.hit_end_of_list(1.0));
+    }
+
+    #[test]
+    fn go_inside_the_list_ignores_wrap() {
+        for wrap in [true, false] {
+            let mut exec = executor(3, 1, wrap);
+            exec.go();
+            assert_eq!(exec.current_cue_index, 2);
+            exec.go_back();
+            assert_eq!(exec.current_cue_index, 1);
+            assert_eq!(exec.end_of_list_time, None);
+        }
+    }
+
+    #[test]
+    fn disabled_last_cue_is_the_end_without_wrap() {
+        let mut exec = executor(3, 1, false);
+        exec.cue_list[2].disabled = true;
+        exec.go();
+        assert_eq!(exec.current_cue_index, 1);
+        assert!(exec.hit_end_of_list(1.0));
+    }
+
+    #[test]
+    fn follow_stops_at_the_end_without_wrap() {
+        let mut exec = executor(2, 1, false);
+        exec.cue_list[1].follow = Some(0.0);
+        exec.fader_level = 1.0;
+        exec.update_follow();
+        assert_eq!(exec.current_cue_index, 1);
+        assert_eq!(exec.end_of_list_time, None);
+
+        exec.wrap = true;
+        exec.update_follow();
+        assert_eq!(exec.current_cue_index, 0);
+    }
+
+    #[test]
+    fn follow_chains_wrap_only_when_the_executor_does() {
+        let mut exec = executor(3, 0, true);
+        exec.cue_list[2].follow = Some(1.0);
+        exec.cue_list[0].follow = Some(1.0);
+        let chains = exec.follow_chains();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].cues, [2, 0, 1]);
+
+        exec.wrap = false;
+        let chains = exec.follow_chains();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].cues, [0, 1]);
+        assert!(!chains[0].loops);
+    }
+
+    #[test]
+    fn follow_chain_runtime_adds_fades_and_follows() {
+        let mut exec = executor(3, 0, false);
+        for (cue, fade) in exec.cue_list.iter_mut().zip([1.0, 2.0, 4.0]) {
+            cue.fade_time = fade;
+            cue.delay = 10.0;
+        }
+        exec.cue_list[0].follow = Some(0.5);
+        exec.cue_list[1].follow = Some(0.25);
+        let chains = exec.follow_chains();
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].starts, [0.0, 1.5, 3.75]);
+        assert_eq!(chains[0].runtime, 7.75);
+    }
+
+    #[test]
+    fn cue_numbers_past_the_last_whole_number_are_refused() {
+        let last = CueNumber::whole(CueNumber::MAX_WHOLE).unwrap();
+        assert!("4294966.999".parse::<CueNumber>().is_ok());
+        assert_eq!(last.next_whole(), None);
+        assert_eq!(
+            "4294965.5".parse::<CueNumber>().unwrap().next_whole(),
+            Some(last)
+        );
+        assert!("4294967".parse::<CueNumber>().is_err());
+        assert!("4294967.295".parse::<CueNumber>().is_err());
+        assert!(CueNumber::from_f64(4294967.0).is_err());
+        assert!(CueNumber::from_f64(-1.0).is_err());
+        assert!(CueNumber::from_f64(f64::NAN).is_err());
+        assert_eq!(CueNumber::from_f64(2.5), "2.5".parse());
+    }
+
+    #[test]
+    fn next_cue_number_fails_after_the_largest_cue() {
+        let mut exec = Executor::new(0);
+        assert_eq!(exec.next_cue_number(), Ok(CueNumber::FIRST));
+        exec.insert_cue(Cue::new("4294966.5".parse().unwrap()))
+            .unwrap();
+        assert!(exec.next_cue_number().is_err());
+    }
+
+    /// Track of `duration` with the given edit points
+    fn track(
+        duration: f32,
+        start: f32,
+        end: Option<f32>,
+        fade_in: f32,
+        fade_out: f32,
+    ) -> AudioTrack {
+        let mut track = AudioTrack::new(0, "Track".into(), "track.wav".into());
+        track.duration = duration;
+        track.start_point = start;
+        track.end_point = end;
+        track.fade_in = fade_in;
+        track.fade_out = fade_out;
+        track
+    }
+
+    fn assert_ranges_valid(track: &AudioTrack) {
+        for range in [
+            track.fade_in_range(),
+            track.fade_out_range(),
+            track.start_range(),
+            track.end_range(),
+        ] {
+            assert!(
+                range.start() <= range.end(),
+                "inverted {range:?} for {track:?}"
+            );
+        }
+    }
+
+    /// Clamped values sit within their ranges, give or take float rounding of the sums
+    fn assert_within_ranges(track: &AudioTrack) {
+        let within = |range: RangeInclusive<f32>, value: f32| {
+            *range.start() - 1e-4 <= value && value <= *range.end() + 1e-4
+        };
+        assert!(within(track.fade_in_range(), track.fade_in), "{track:?}");
+        assert!(within(track.fade_out_range(), track.fade_out), "{track:?}");
+        assert!(within(track.start_range(), track.start_point), "{track:?}");
+        assert!(within(track.end_range(), track.end()), "{track:?}");
+    }
+
+    #[test]
+    fn end_just_after_start_with_large_fades() {
+        let mut track = track(10.0, 5.0, Some(5.1), 4.0, 4.0);
+        assert_ranges_valid(&track);
+        track.clamp_edit_points();
+        assert_ranges_valid(&track);
+        assert_within_ranges(&track);
+        assert!(track.fade_in + track.fade_out <= 0.1 + f32::EPSILON);
+    }
+
+    #[test]
+    fn zero_duration() {
+        let mut track = track(0.0, 3.0, None, 2.0, 1.0);
+        assert_ranges_valid(&track);
+        track.clamp_edit_points();
+        assert_ranges_valid(&track);
+        assert_within_ranges(&track);
+        assert_eq!(
+            (track.start_point, track.fade_in, track.fade_out),
+            (0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn end_before_start() {
+        let mut track = track(10.0, 9.0, Some(2.0), 8.0, 8.0);
+        assert_ranges_valid(&track);
+        track.clamp_edit_points();
+        assert_ranges_valid(&track);
+        assert_within_ranges(&track);
+    }
+
+    #[test]
+    fn out_of_track_values() {
+        let mut track = track(5.0, -1.0, Some(20.0), -2.0, 7.0);
+        assert_ranges_valid(&track);
+        track.clamp_edit_points();
+        assert_ranges_valid(&track);
+        assert_within_ranges(&track);
+        assert_eq!(track.end_point, None);
+    }
+
+    #[test]
+    fn valid_values_are_kept() {
+        let mut track = track(10.0, 1.0, Some(9.0), 2.0, 3.0);
+        track.clamp_edit_points();
+        assert_eq!(track.start_point, 1.0);
+        assert_eq!(track.end_point, Some(9.0));
+        assert_eq!((track.fade_in, track.fade_out), (2.0, 3.0));
+        assert_eq!(track.fade_in_range(), 0.0..=5.0);
+        assert_eq!(track.end_range(), 6.0..=10.0);
+    }
+
+    #[test]
+    fn ranges_never_invert() {
+        let values = [-1.0, 0.0, 0.1, 2.5, 5.0, 9.9, 10.0, 30.0];
+        for duration in [0.0, 10.0] {
+            for start in values {
+                for end in values.map(Some).into_iter().chain([None]) {
+                    for fade_in in values {
+                        for fade_out in values {
+                            let mut track = track(duration, start, end, fade_in, fade_out);
+                            assert_ranges_valid(&track);
+                            track.clamp_edit_points();
+                            assert_ranges_valid(&track);
+                            assert_within_ranges(&track);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! HTTP API
+//!
+//! Optional embedded server for remote status and control, off until started with
+//! a bind address:
+//! - `GET /status`: masters, executors and audio transport as JSON
+//! - `POST /command`: runs `{"command": "..."}` as a console command line
+//!   (`go exec 1`, `go exec 1 cue 3`, `blackout`...), the same grammar as the command line
+//!
+//! There is no authentication. No CORS header is sent, so web pages can't read the
+//! status, and `/command` only takes `application/json`, which browsers can't send
+//! cross-origin without a preflight the server never allows.
+//!
+//! Connections are served on the tokio runtime. Requests are handed to the frame
+//! loop through a channel and answered there, so they see and change the live state.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::console::execute_command_line;
+use crate::ui::ConsoleState;
+
+/// Largest request accepted, headers and body together
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+/// How long a client has to send its whole request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a connection waits for the frame loop to answer
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid address, expected IP:Port")]
+    InvalidAddress,
+    #[error("Port {0} is already in use")]
+    PortInUse(u16),
+    #[error("Binding Error: {0}")]
+    Binding(String),
+}
+
+enum ApiRequest {
+    Status(oneshot::Sender<Value>),
+    Command(String, oneshot::Sender<Result<(), String>>),
+}
+
+pub struct HttpApi {
+    address: SocketAddr,
+    requests: Receiver<ApiRequest>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for HttpApi {
+    fn drop(&mut self) {
+        // The task owns the listener, aborting it releases the port
+        self.task.abort();
+    }
+}
+
+impl HttpApi {
+    /// Starts listening on `address` (IP:Port). Must be called from the tokio runtime.
+    pub fn start(address: &str) -> Result<Self, Error> {
+        let address: SocketAddr = address.trim().parse().map_err(|_| Error::InvalidAddress)?;
+        let listener = std::net::TcpListener::bind(address).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AddrInUse => Error::PortInUse(address.port()),
+            _ => Error::Binding(e.to_string()),
+        })?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| Error::Binding(e.to_string()))?;
+        let listener =
+            TcpListener::from_std(listener).map_err(|e| Error::Binding(e.to_string()))?;
+
+        let (sender, requests) = unbounded();
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve(stream, sender.clone()));
+                    }
+                    Err(e) => println!("HTTP accept error: {e}"),
+                }
+            }
+        });
+        Ok(Self {
+            address,
+            requests,
+            task,
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Answers the requests received since the last frame
+    pub fn poll(&self, state: &mut ConsoleState) {
+        while let Ok(request) = self.requests.try_recv() {
+            match request {
+                ApiRequest::Status(reply) => {
+                    let _ = reply.send(status(state));
+                }
+                ApiRequest::Command(command, reply) => {
+                    // The error line shows the operator's own commands, keep it as it was
+                    let operator_error = state.command_error.take();
+                    execute_command_line(state, &command);
+                    let result = state.command_error.take();
+                    state.command_error = operator_error;
+                    let _ = reply.send(match result {
+                        Some(error) => Err(error),
+                        None => Ok(()),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Answers the requests of the running HTTP API, if any
+pub fn poll_http_api(state: &mut ConsoleState) {
+    if let Some(api) = state.http_api.1.take() {
+        api.poll(state);
+        state.http_api.1 = Some(api);
+    }
+}
+
+fn status(state: &ConsoleState) -> Value {
+    let executors: Vec<Value> = state
+        .executors
+        .iter()
+        .map(|exec| {
+            let cue = exec.cue_list.get(exec.current_cue_index);
+            json!({
+                "executor": exec.id + 1,
+                "fader": exec.fader_level,
+                "cue": cue.map(|c| c.id.to_string()),
+                "cue_name": cue.map(|c| c.name.clone()),
+                "cue_count": exec.cue_list.len(),
+                "fading": exec.is_fading,
+                "locked": exec.locked,
+            })
+        })
+        .collect();
+    let playing = state
+        .audio_engine
+        .as_ref()
+        .is_some_and(|engine| engine.is_any_playing());
+    json!({
+        "master_dimmer": state.master_dimmer,
+        "master_volume": state.master_volume,
+        "frozen": state.frozen,
+        "executors": executors,
+        "audio": {
+            "playing": playing,
+            "index": state.audio_index + 1,
+            "track": state.audio_tracks.get(state.audio_index).map(|t| t.name.clone()),
+            "track_count": state.audio_tracks.len(),
+        },
+    })
+}
+
+/// Serves one connection: a single request, then the connection is closed
+async fn serve(mut stream: TcpStream, requests: Sender<ApiRequest>) {
+    let (code, body) = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await
+    {
+        Ok(Ok(request)) => route(request, &requests).await,
+        Ok(Err(e)) => (400, json!({ "error": e })),
+        Err(_) => (408, json!({ "error": "Timed out waiting for the request" })),
+    };
+    let body = body.to_string();
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        415 => "Unsupported Media Type",
+        _ => "Service Unavailable",
+    };
+    let response = format!(
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn route(request: Request, requests: &Sender<ApiRequest>) -> (u16, Value) {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/status") => {
+            let (reply, answer) = oneshot::channel();
+            if requests.send(ApiRequest::Status(reply)).is_err() {
+                return (503, json!({ "error": "The console is not running" }));
+            }
+            match tokio::time::timeout(RESPONSE_TIMEOUT, answer).await {
+                Ok(Ok(status)) => (200, status),
+                _ => (503, json!({ "error": "The console did not answer" })),
+            }
+        }
+        ("POST", "/command") => {
+            if !request.is_json() {
+                return (
+                    415,
+                    json!({ "error": "Expected Content-Type: application/json" }),
+                );
+            }
+            let command = serde_json::from_str::<Value>(&request.body)
+                .ok()
+                .and_then(|body| body.get("command")?.as_str().map(str::to_string));
+            let Some(command) = command else {
+                return (
+                    400,
+                    json!({ "error": "Expected a body like {\"command\": \"go exec 1\"}" }),
+                );
+            };
+            let (reply, answer) = oneshot::channel();
+            if requests
+                .send(ApiRequest::Command(command.trim().to_string(), reply))
+                .is_err()
+            {
+                return (503, json!({ "error": "The console is not running" }));
+            }
+            match tokio::time::timeout(RESPONSE_TIMEOUT, answer).await {
+                Ok(Ok(Ok(()))) => (200, json!({ "ok": true })),
+                Ok(Ok(Err(error))) => (400, json!({ "error": error })),
+                _ => (503, json!({ "error": "The console did not answer" })),
+            }
+        }
+        (method, path) => (
+            404,
+            json!({ "error": format!("No route for {method} {path}") }),
+        ),
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    content_type: Option<String>,
+    body: String,
+}
+
+impl Request {
+    fn is_json(&self) -> bool {
+        self.content_type.as_deref().is_some_and(|content_type| {
+            let mime = content_type.split(';').next().unwrap_or_default();
+            mime.trim().eq_ignore_ascii_case("application/json")
+        })
+    }
+}
+
+/// Reads the request line, headers and body
+async fn read_request(stream: &mut TcpStream) -> Result<Request, String> {
+    let mut data = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if data.len() > MAX_REQUEST_SIZE {
+            return Err("Request too large".to_string());
+        }
+        let read = stream.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if read == 0 {
+            return Err("Incomplete request".to_string());
+        }
+        data.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err("Invalid request line".to_string());
+    };
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    };
+    let content_length = header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let content_type = header("content-type").map(str::to_string);
+    if header_end + content_length > MAX_REQUEST_SIZE {
+        return Err("Request too large".to_string());
+    }
+
+    while data.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if read == 0 {
+            return Err("Incomplete body".to_string());
+        }
+        data.extend_from_slice(&chunk[..read]);
+    }
+    let body = String::from_utf8_lossy(&data[header_end..header_end + content_length]).to_string();
+    // Query strings are not used
+    let path = path.split('?').next().unwrap_or(path);
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        content_type,
+        body,
+    })
+}
@@ -2,7 +2,10 @@ mod audio;
 mod console;
 mod dmx_output;
 mod dmx_types;
+mod http_api;
 mod osc;
+mod runtime;
+mod show_file;
 mod ui;
 mod show {
     pub enum ShowAction {
@@ -13,21 +16,66 @@ mod show {
 }
 
 use eframe::NativeOptions;
-use egui::Vec2;
+use std::path::PathBuf;
 use std::sync::Mutex;
 
 use ui::{ConsoleState, Tab, show_audio_tab, show_dmx_console, show_liveshow_tab};
 
-use crate::{dmx_output::mix_executor_outputs, ui::show_midi_osc_tab};
+use crate::{
+    show_file::ShowFile,
+    ui::{
+        LayoutPreset, apply_min_window_size, apply_theme, poll_file_dialog, show_layout_menu,
+        show_midi_osc_tab, show_new_show_confirm, show_show_file_buttons, show_status_bar,
+        show_theme_menu,
+    },
+};
 
 pub struct AppState {
     state: Mutex<ConsoleState>,
 }
 
+/// eframe storage key of the layout settings
+const LAYOUT_KEY: &str = "layout";
+/// eframe storage key of the open tabs
+const TABS_KEY: &str = "tabs";
+/// eframe storage key of the theme settings
+const THEME_KEY: &str = "theme";
+
 impl AppState {
-    fn new() -> Self {
+    fn new(cc: &eframe::CreationContext<'_>, args: Args) -> Self {
+        let mut state = ConsoleState::default();
+        if let Some(layout) = cc.storage.and_then(|s| eframe::get_value(s, LAYOUT_KEY)) {
+            state.layout = layout;
+        }
+        if let Some(tabs) = cc.storage.and_then(|s| eframe::get_value(s, TABS_KEY)) {
+            state.restore_tab_selection(tabs);
+        }
+        if let Some(theme) = cc.storage.and_then(|s| eframe::get_value(s, THEME_KEY)) {
+            state.theme = theme;
+        }
+        if let Some(path) = args.show {
+            match ShowFile::load_from_path(&path) {
+                Ok(show) => {
+                    show.apply_to(&mut state);
+                    state.show_path = Some(path);
+                }
+                Err(e) => state.show_file_error = Some(e.to_string()),
+            }
+        }
+        if let Some(address) = args.osc {
+            match crate::osc::OSCManager::from(address) {
+                Ok(manager) => state.osc_manager.1 = Some(manager),
+                Err(e) => state.osc_error = Some(e.to_string()),
+            }
+        }
+        if let Some(address) = args.http {
+            match crate::http_api::HttpApi::start(&address) {
+                Ok(api) => state.http_api.1 = Some(api),
+                Err(e) => state.http_api_error = Some(e.to_string()),
+            }
+        }
         Self {
-            state: Mutex::new(ConsoleState::default()),
+            state: Mutex::new(state),
         }
     }
 }
@@ -35,15 +83,23 @@ impl AppState {
 impl eframe::App for AppState {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         let mut state = self.state.lock().unwrap();
-        if let Some(osc_manager) = &mut state.osc_manager.1 {
-            crate::osc::handle_osc(osc_manager.get_osc(), &mut state);
-        }
+        apply_min_window_size(ctx, &mut state);
+        apply_theme(ctx, &mut state);
+        poll_file_dialog(&mut state);
         egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut state.selected_tab, Tab::DmxConsole, "DMX Console");
                 ui.selectable_value(&mut state.selected_tab, Tab::Audio, "Audio");
                 ui.selectable_value(&mut state.selected_tab, Tab::MidiOsc, "MIDI/OSC");
                 ui.selectable_value(&mut state.selected_tab, Tab::Show, "Show");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("New Show").clicked() {
+                        state.new_show_confirm = true;
+                    }
+                    show_show_file_buttons(ui, &mut state);
+                    show_layout_menu(ui, &mut state);
+                    show_theme_menu(ui, &mut state);
+                });
                 if ui.input(|i| i.key_pressed(egui::Key::Num1) && i.modifiers.command) {
                     state.selected_tab = Tab::DmxConsole;
                 }
@@ -64,31 +120,97 @@ impl eframe::App for AppState {
             });
         });
 
+        show_status_bar(ctx, &state);
+
         match state.selected_tab {
             Tab::DmxConsole => show_dmx_console(ctx, &mut state),
             Tab::Audio => show_audio_tab(ctx, &mut state),
             Tab::MidiOsc => show_midi_osc_tab(ctx, &mut state),
             Tab::Show => show_liveshow_tab(ctx, &mut state),
         }
-        // Send DMX Values
-        mix_executor_outputs(&mut state);
+        show_new_show_confirm(ctx, &mut state);
+        // OSC, audio and DMX output
+        runtime::tick(&mut state);
 
         ctx.request_repaint();
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let state = self.state.lock().unwrap();
+        eframe::set_value(storage, LAYOUT_KEY, &state.layout);
+        eframe::set_value(storage, TABS_KEY, &state.tab_selection());
+        eframe::set_value(storage, THEME_KEY, &state.theme);
+    }
+}
+
+/// Command line options
+#[derive(Default)]
+struct Args {
+    /// `--show <file>`: show file opened at start
+    show: Option<PathBuf>,
+    /// `--headless`: run the show without a window, needs `--show`
+    headless: bool,
+    /// `--osc <ip:port>`: listen for OSC from the start
+    osc: Option<String>,
+    /// `--http <ip:port>`: serve the HTTP API from the start
+    http: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut input = std::env::args().skip(1);
+    while let Some(arg) = input.next() {
+        match arg.as_str() {
+            "--show" => {
+                let path = input.next().ok_or("--show needs a show file")?;
+                args.show = Some(PathBuf::from(path));
+            }
+            "--headless" => args.headless = true,
+            "--osc" => args.osc = Some(input.next().ok_or("--osc needs an ip:port address")?),
+            "--http" => args.http = Some(input.next().ok_or("--http needs an ip:port address")?),
+            other => return Err(format!("Unknown argument: {other}")),
+        }
+    }
+    if args.headless && args.show.is_none() {
+        return Err("--headless needs --show <file>".to_string());
+    }
+    Ok(args)
 }
 
 #[tokio::main]
 async fn main() -> eframe::Result<()> {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!(
+                "Usage: q-gui [--show <file> [--headless]] [--osc <ip:port>] [--http <ip:port>]"
+            );
+            std::process::exit(2);
+        }
+    };
+    if args.headless
+        && let Some(show) = &args.show
+    {
+        if let Err(e) = runtime::run_headless(show, args.osc.clone(), args.http.clone()).await {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_title("Egui Live")
-            .with_min_inner_size(Vec2::new(1280.0, 800.0)),
+            .with_min_inner_size(LayoutPreset::Compact.min_window_size()),
+        // Window size and position are restored from the eframe storage
+        persist_window: true,
         ..Default::default()
     };
 
     eframe::run_native(
         "DMX Console",
         options,
-        Box::new(|_cc| Ok(Box::new(AppState::new()))),
+        Box::new(|cc| Ok(Box::new(AppState::new(cc, args)))),
     )
 }
@@ -1,17 +1,19 @@
-use crossbeam_channel::{bounded, select, unbounded};
-use rosc::OscPacket;
+use crossbeam_channel::{TryRecvError, bounded, select, unbounded};
+use rosc::{OscMessage, OscPacket, OscType};
 use std::{
+    collections::HashMap,
+    io::ErrorKind,
     net::{Ipv4Addr, SocketAddr, UdpSocket},
-    time::Duration,
+    thread::JoinHandle,
+    time::{Duration, Instant},
 };
 use thiserror::Error;
-use tokio::task::JoinHandle;
 
+use crate::audio::MAX_MASTER_VOLUME;
 use crate::ui::{ConsoleState, audio_go};
 
 pub fn handle_osc(opt: Option<OscPacket>, state: &mut ConsoleState) {
     use crate::osc::is_osc_address;
-    use rosc::OscType;
     let osc_addresser = &state.osc_address_manager.clone();
     if is_osc_address(&opt, &osc_addresser.audio_go) {
         audio_go(state, state.audio_tracks.len());
@@ -22,125 +24,119 @@ pub fn handle_osc(opt: Option<OscPacket>, state: &mut ConsoleState) {
         }
     }
     if is_osc_address(&opt, &osc_addresser.master_volume) {
-        if let Some(osc) = &opt {
-            match osc {
-                OscPacket::Message(osc_message) => {
-                    osc_message.args.iter().for_each(|osc_type| match osc_type {
-                        OscType::Float(x) if (0.0..=1.5).contains(x) => {
-                            state.master_volume = *x;
-                        }
-                        OscType::Double(x) if (0.0..=150.0).contains(x) => {
-                            state.master_volume = (*x / 100.0) as f32;
-                        }
-                        OscType::Int(x) if (0..=150).contains(x) => {
-                            state.master_volume = (*x / 100) as f32;
-                        }
-                        OscType::Long(x) if (0..=1500).contains(x) => {
-                            state.master_volume = (*x / 1000) as f32;
-                        }
-                        _ => {
-                            println!("Invalid OSCType or Invalid Value {osc:?} for Master Volume");
-                        }
-                    });
-                }
-                _ => {}
+        state
+            .osc_touched
+            .insert(OSCTarget::MasterVolume, Instant::now());
+        match osc_value(&opt) {
+            Some(x) => {
+                state.master_volume =
+                    osc_addresser.master_volume_range.normalize(x) * MAX_MASTER_VOLUME;
             }
+            None => println!("Invalid OSCType or Invalid Value {opt:?} for Master Volume"),
         }
     }
     if is_osc_address(&opt, &osc_addresser.master_dmx) {
-        if let Some(osc) = &opt {
-            match osc {
-                OscPacket::Message(osc_message) => {
-                    osc_message.args.iter().for_each(|osc_type| match osc_type {
-                        OscType::Float(x) if (0.0..=1.0).contains(x) => {
-                            state.master_dimmer = *x;
-                        }
-                        OscType::Int(x) if (0..=100).contains(x) => {
-                            state.master_dimmer = (*x / 100) as f32;
-                        }
-                        OscType::Double(x) if (0.0..=100.0).contains(x) => {
-                            state.master_dimmer = (*x / 100.0) as f32;
-                        }
-                        OscType::Long(x) if (0..=1000).contains(x) => {
-                            state.master_dimmer = (x / 1000) as f32;
-                        }
-                        _ => {
-                            println!("Invalid OSCType or Invalid Value {osc:?} for Master DMX");
-                        }
-                    });
-                }
-                _ => {}
-            }
+        state
+            .osc_touched
+            .insert(OSCTarget::MasterDimmer, Instant::now());
+        match osc_value(&opt) {
+            Some(x) => state.master_dimmer = osc_addresser.master_dmx_range.normalize(x),
+            None => println!("Invalid OSCType or Invalid Value {opt:?} for Master DMX"),
         }
     }
-    let exec_dimmer = state.executors.iter_mut().find(|exec| {
-        let id = exec.id as i8 + 1;
-        is_osc_address(
-            &opt,
-            format!(
-                "{}{id}{}",
-                state.osc_address_manager.executor_identifier,
-                state.osc_address_manager.executor_dimmer
-            ),
-        )
-    });
-    if let Some(exec) = exec_dimmer {
-        if let Some(osc) = &opt {
-            match osc {
-                OscPacket::Message(osc_message) => {
-                    osc_message.args.iter().for_each(|osc_type| match osc_type {
-                        OscType::Float(x) if (0.0..=1.0).contains(x) => {
-                            if exec.cue_list.len() > 0 {
-                                exec.fader_level = *x;
-                            }
-                        }
-                        _ => {}
-                    })
-                }
-                _ => {}
-            }
+    // A pattern address can act on several executors at once
+    let mut matched_executor = false;
+    for exec in state.executors.iter_mut() {
+        let id = exec.id + 1;
+        let prefix = format!("{}{id}", osc_addresser.executor_identifier);
+        let dimmer = is_osc_address(&opt, format!("{prefix}{}", osc_addresser.executor_dimmer));
+        let go = is_osc_address(&opt, format!("{prefix}{}", osc_addresser.executor_go));
+        let go_back = is_osc_address(&opt, format!("{prefix}{}", osc_addresser.executor_go_back));
+        if !(dimmer || go || go_back) {
+            continue;
+        }
+        matched_executor = true;
+        state
+            .osc_touched
+            .insert(OSCTarget::Executor(exec.id), Instant::now());
+        if exec.cue_list.is_empty() {
+            println!("Executor {id} has no cues, ignoring OSC {opt:?}");
+            continue;
+        }
+        if dimmer && let Some(x) = osc_value(&opt) {
+            exec.fader_level = osc_addresser.executor_dimmer_range.normalize(x);
+        }
+        if go && is_rising_edge(&mut exec.osc_go_level, &opt) {
+            exec.go();
+        }
+        if go_back && is_rising_edge(&mut exec.osc_go_back_level, &opt) {
+            exec.go_back();
         }
     }
-    let exec_go = state.executors.iter_mut().find(|exec| {
-        let id = exec.id as i8 + 1;
-        is_osc_address(
-            &opt,
-            format!(
-                "{}{id}{}",
-                state.osc_address_manager.executor_identifier,
-                state.osc_address_manager.executor_go
-            ),
-        )
-    });
-    if let Some(exec) = exec_go {
-        exec.go();
+    if !matched_executor
+        && let Some(OscPacket::Message(message)) = &opt
+        && message.addr.starts_with(&osc_addresser.executor_identifier)
+    {
+        println!("OSC address {} matches no executor", message.addr);
     }
-    let exec_go_back = state.executors.iter_mut().find(|exec| {
-        let id = exec.id as i8 + 1;
-        is_osc_address(
-            &opt,
-            format!(
-                "{}{id}{}",
-                state.osc_address_manager.executor_identifier,
-                state.osc_address_manager.executor_go_back
-            ),
-        )
-    });
-    if let Some(exec) = exec_go_back {
-        exec.go_back();
+}
+
+/// Control an incoming OSC message acted on, for the UI to highlight
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum OSCTarget {
+    MasterVolume,
+    MasterDimmer,
+    /// Executor by id (0-based)
+    Executor(u32),
+}
+
+/// First numeric argument of a message, whatever its OSC type
+fn osc_value(opt: &Option<OscPacket>) -> Option<f32> {
+    let Some(OscPacket::Message(message)) = opt else {
+        return None;
+    };
+    message.args.iter().find_map(|arg| match arg {
+        OscType::Float(x) => Some(*x),
+        OscType::Double(x) => Some(*x as f32),
+        OscType::Int(x) => Some(*x as f32),
+        OscType::Long(x) => Some(*x as f32),
+        OscType::Bool(x) => Some(*x as u8 as f32),
+        _ => None,
+    })
+}
+
+/// Level above which an OSC button counts as pressed
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Whether a trigger message presses the button: its value crosses above the threshold
+/// from below. Messages without a numeric argument are momentary and always trigger.
+fn is_rising_edge(last_level: &mut f32, opt: &Option<OscPacket>) -> bool {
+    if !matches!(opt, Some(OscPacket::Message(_))) {
+        return false;
     }
+    let Some(level) = osc_value(opt) else {
+        return true;
+    };
+    let was_pressed = *last_level > TRIGGER_THRESHOLD;
+    *last_level = level;
+    !was_pressed && level > TRIGGER_THRESHOLD
 }
 
+/// How long the receive thread sleeps when no packet is waiting
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Invalid IP:Port formatting")]
     InvalidIpFormatting,
     #[error("Binding Error: {0}")]
     BindingError(String),
+    #[error("Port {0} is already in use")]
+    PortInUse(u16),
 }
 pub struct OSCManager {
     thread_stopper: crossbeam_channel::Sender<()>,
-    thread_handle: JoinHandle<()>,
+    thread_handle: Option<JoinHandle<()>>,
     osc_receiver: crossbeam_channel::Receiver<Option<OscPacket>>,
     osc_history: Vec<OscPacket>,
 }
@@ -150,7 +146,12 @@ impl Drop for OSCManager {
         } else {
             println!("Stopping thread message failed to send");
         }
-        self.thread_handle.abort_handle().abort();
+        // The thread owns the socket, so joining it guarantees the port is released
+        if let Some(handle) = self.thread_handle.take()
+            && handle.join().is_err()
+        {
+            println!("OSC thread panicked");
+        }
         println!("Dropping OSC Manager");
     }
 }
@@ -165,46 +166,51 @@ impl OSCManager {
                     port,
                 )) {
                     Ok(socket) => {
-                        let _ = socket.set_read_timeout(Some(Duration::from_millis(50)));
+                        socket
+                            .set_nonblocking(true)
+                            .map_err(|e| Error::BindingError(e.to_string()))?;
                         let channels = unbounded();
                         let osc_channels = bounded(1);
-                        let handle = tokio::spawn(async move {
+                        let handle = std::thread::spawn(move || {
                             let thread_stop = channels.1;
                             let osc_sender = osc_channels.0;
                             let mut buffer = [0u8; rosc::decoder::MTU];
                             'task: loop {
-                                select! {
-                                    recv(thread_stop) -> _msg => {
-                                        break 'task;
-                                    }
-                                    send(osc_sender,match socket.recv_from(&mut buffer) {
-                                        Ok((size, _)) => {
-                                            if let Ok((_, packet)) =
-                                                rosc::decoder::decode_udp(&buffer[..size])
-                                            {
-                                                Some(packet)
-                                            } else {
-                                                None
-                                            }
-                                        }
-                                        Err(_) => {None}
-                                    }) -> res => {
-                                        match res {
-                                            Ok(_) => {},
-                                            Err(_) => {},
+                                // A dropped sender counts as a stop request too
+                                if !matches!(thread_stop.try_recv(), Err(TryRecvError::Empty)) {
+                                    break 'task;
+                                }
+                                match socket.recv_from(&mut buffer) {
+                                    Ok((size, _)) => {
+                                        let Ok((_, packet)) =
+                                            rosc::decoder::decode_udp(&buffer[..size])
+                                        else {
+                                            continue;
+                                        };
+                                        select! {
+                                            recv(thread_stop) -> _msg => break 'task,
+                                            send(osc_sender, Some(packet)) -> _res => {}
                                         }
                                     }
+                                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                                        std::thread::sleep(POLL_INTERVAL);
+                                    }
+                                    Err(e) => {
+                                        println!("OSC receive error: {e}");
+                                        std::thread::sleep(POLL_INTERVAL);
+                                    }
                                 }
                             }
                             println!("OSC Thread stopped");
                         });
                         Ok(Self {
                             thread_stopper: channels.0,
-                            thread_handle: handle,
+                            thread_handle: Some(handle),
                             osc_receiver: osc_channels.1,
                             osc_history: Default::default(),
                         })
                     }
+                    Err(e) if e.kind() == ErrorKind::AddrInUse => Err(Error::PortInUse(port)),
                     Err(e) => Err(Error::BindingError(e.to_string())),
                 }
             }
@@ -228,17 +234,162 @@ impl OSCManager {
     }
 }
 
+/// Minimum time between two feedback messages on the same address
+const FEEDBACK_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Sends console state back to a remote OSC surface
+pub struct OSCFeedback {
+    socket: UdpSocket,
+    target: SocketAddr,
+    /// Last value sent on each address, and when
+    last_sent: HashMap<String, (OscType, Instant)>,
+}
+
+impl OSCFeedback {
+    pub fn from(target: impl Into<String>) -> Result<Self, Error> {
+        use scan_fmt::scan_fmt;
+        let target = target.into();
+        let (a, b, c, d, port) = scan_fmt!(&target, "{}.{}.{}.{}:{}", u8, u8, u8, u8, u16)
+            .map_err(|_| Error::InvalidIpFormatting)?;
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .map_err(|e| Error::BindingError(e.to_string()))?;
+        Ok(Self {
+            socket,
+            target: SocketAddr::new(std::net::IpAddr::V4(Ipv4Addr::new(a, b, c, d)), port),
+            last_sent: Default::default(),
+        })
+    }
+
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Sends `value` on `addr` if it changed since the last send and the address
+    /// is not within its debounce window. Call it every frame with the current
+    /// value so the latest one goes out once the window has passed.
+    pub fn send(&mut self, addr: &str, value: OscType) {
+        if let Some((last, at)) = self.last_sent.get(addr)
+            && (*last == value || at.elapsed() < FEEDBACK_DEBOUNCE)
+        {
+            return;
+        }
+        let packet = OscPacket::Message(OscMessage {
+            addr: addr.to_string(),
+            args: vec![value.clone()],
+        });
+        match rosc::encoder::encode(&packet) {
+            Ok(bytes) => {
+                if let Err(e) = self.socket.send_to(&bytes, self.target) {
+                    println!("OSC feedback to {} failed: {e}", self.target);
+                }
+            }
+            Err(e) => println!("OSC feedback encoding failed: {e}"),
+        }
+        self.last_sent
+            .insert(addr.to_string(), (value, Instant::now()));
+    }
+}
+
+/// Mirrors the audio section on the feedback target, using the same addresses as the inputs
+pub fn send_feedback(state: &mut ConsoleState) {
+    let Some(feedback) = &mut state.osc_feedback.1 else {
+        return;
+    };
+    let naming = &state.osc_address_manager;
+    let playing = state
+        .audio_engine
+        .as_ref()
+        .is_some_and(|engine| engine.is_any_playing());
+    let volume = naming
+        .master_volume_range
+        .denormalize(state.master_volume / MAX_MASTER_VOLUME);
+    feedback.send(&naming.master_volume, OscType::Float(volume));
+    feedback.send(&naming.audio_go, OscType::Float(playing as u8 as f32));
+    feedback.send(&naming.audio_stop, OscType::Float(!playing as u8 as f32));
+}
+
+/// Whether the received message is for `addr`. The message address may be an
+/// OSC address pattern (`*`, `?`, `[a-z]`, `{a,b}`) addressing several handlers at once.
 pub fn is_osc_address(opt: &Option<OscPacket>, addr: impl std::fmt::Display) -> bool {
     let address = addr.to_string();
     match opt {
         Some(p) => match p {
-            OscPacket::Message(osc_message) => osc_message.addr == address,
+            OscPacket::Message(osc_message) => osc_address_matches(&osc_message.addr, &address),
             OscPacket::Bundle(osc_bundle) => false,
         },
         None => false,
     }
 }
 
+/// Characters that make an OSC address a pattern rather than a literal address
+const OSC_PATTERN_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+/// OSC 1.0 address pattern matching, part by part between the `/`
+fn osc_address_matches(pattern: &str, address: &str) -> bool {
+    if !pattern.contains(OSC_PATTERN_CHARS) {
+        return pattern == address;
+    }
+    let pattern_parts: Vec<&str> = pattern.split('/').collect();
+    let address_parts: Vec<&str> = address.split('/').collect();
+    pattern_parts.len() == address_parts.len()
+        && pattern_parts
+            .iter()
+            .zip(&address_parts)
+            .all(|(pattern, part)| {
+                let pattern: Vec<char> = pattern.chars().collect();
+                let part: Vec<char> = part.chars().collect();
+                osc_part_matches(&pattern, &part)
+            })
+}
+
+fn osc_part_matches(pattern: &[char], part: &[char]) -> bool {
+    match pattern.split_first() {
+        None => part.is_empty(),
+        Some(('*', rest)) => (0..=part.len()).any(|skip| osc_part_matches(rest, &part[skip..])),
+        Some(('?', rest)) => !part.is_empty() && osc_part_matches(rest, &part[1..]),
+        Some(('[', rest)) => {
+            let Some(end) = rest.iter().position(|&c| c == ']') else {
+                return false;
+            };
+            let Some((&c, part_rest)) = part.split_first() else {
+                return false;
+            };
+            osc_char_class_contains(&rest[..end], c)
+                && osc_part_matches(&rest[end + 1..], part_rest)
+        }
+        Some(('{', rest)) => {
+            let Some(end) = rest.iter().position(|&c| c == '}') else {
+                return false;
+            };
+            let after = &rest[end + 1..];
+            rest[..end].split(|&c| c == ',').any(|alternative| {
+                part.starts_with(alternative) && osc_part_matches(after, &part[alternative.len()..])
+            })
+        }
+        Some((c, rest)) => part.first() == Some(c) && osc_part_matches(rest, &part[1..]),
+    }
+}
+
+/// `[abc]`, `[a-z]` and negated `[!a-z]` character classes
+fn osc_char_class_contains(class: &[char], c: char) -> bool {
+    let (negated, class) = match class.split_first() {
+        Some(('!', rest)) => (true, rest),
+        _ => (false, class),
+    };
+    let mut found = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            found |= (class[i]..=class[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            found |= class[i] == c;
+            i += 1;
+        }
+    }
+    found != negated
+}
+
 #[derive(Clone)]
 pub struct OSCNaming {
     /// The OSC Address name to modify the master volume
@@ -257,6 +408,39 @@ pub struct OSCNaming {
     pub executor_go: String,
     /// Executor GO BACK OSC
     pub executor_go_back: String,
+    /// Values the controller sends for the master volume, from silent to the top of the range
+    pub master_volume_range: OSCRange,
+    /// Values the controller sends for the master dimmer, from 0 to full
+    pub master_dmx_range: OSCRange,
+    /// Values the controller sends for the executor faders, from 0 to full
+    pub executor_dimmer_range: OSCRange,
+}
+
+/// Range of values a controller sends on a fader binding, whatever its convention
+/// (0-1, 0-100, 0-127...). `min` above `max` inverts the fader.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OSCRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl OSCRange {
+    pub const fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+
+    /// Position of `value` in the range, 0.0 at `min` to 1.0 at `max`
+    pub fn normalize(&self, value: f32) -> f32 {
+        if self.min == self.max {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    /// Controller value for a position in the range, for feedback
+    pub fn denormalize(&self, level: f32) -> f32 {
+        self.min + (self.max - self.min) * level
+    }
 }
 
 impl Default for OSCNaming {
@@ -270,6 +454,96 @@ impl Default for OSCNaming {
             executor_dimmer: String::from("/Dimmer"),
             executor_go: String::from("/Go"),
             executor_go_back: String::from("/GoBack"),
+            master_volume_range: OSCRange::new(0.0, MAX_MASTER_VOLUME),
+            master_dmx_range: OSCRange::new(0.0, 1.0),
+            executor_dimmer_range: OSCRange::new(0.0, 1.0),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class_contains(class: &str, c: char) -> bool {
+        osc_char_class_contains(&class.chars().collect::<Vec<_>>(), c)
+    }
+
+    #[test]
+    fn plain_address_is_exact() {
+        assert!(osc_address_matches("/Executor1/Go", "/Executor1/Go"));
+        assert!(!osc_address_matches("/Executor1/Go", "/Executor1/GoBack"));
+        assert!(!osc_address_matches("/Executor1/Go", "/Executor12/Go"));
+    }
+
+    #[test]
+    fn star_matches_within_a_part() {
+        assert!(osc_address_matches("/Executor*/Go", "/Executor1/Go"));
+        assert!(osc_address_matches("/Executor*/Go", "/Executor12/Go"));
+        assert!(osc_address_matches("/Executor*/Go", "/Executor/Go"));
+        assert!(osc_address_matches("/Executor1/*", "/Executor1/GoBack"));
+        assert!(!osc_address_matches("/Executor*/Go", "/Executor1/GoBack"));
+        // Never crosses a `/`
+        assert!(!osc_address_matches("/*", "/Executor1/Go"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_char() {
+        assert!(osc_address_matches("/Executor?/Go", "/Executor1/Go"));
+        assert!(!osc_address_matches("/Executor?/Go", "/Executor12/Go"));
+        assert!(!osc_address_matches("/Executor?/Go", "/Executor/Go"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(osc_address_matches("/Executor[13]/Go", "/Executor3/Go"));
+        assert!(!osc_address_matches("/Executor[13]/Go", "/Executor2/Go"));
+        assert!(osc_address_matches("/Executor[1-3]/Go", "/Executor2/Go"));
+        assert!(!osc_address_matches("/Executor[1-3]/Go", "/Executor4/Go"));
+        assert!(osc_address_matches("/Executor[!1-3]/Go", "/Executor4/Go"));
+        assert!(!osc_address_matches("/Executor[!1-3]/Go", "/Executor2/Go"));
+        assert!(osc_address_matches(
+            "/Executor[1-2][0-9]/Go",
+            "/Executor12/Go"
+        ));
+    }
+
+    #[test]
+    fn unterminated_class_matches_nothing() {
+        assert!(!osc_address_matches("/Executor[1-3/Go", "/Executor2/Go"));
+        assert!(!osc_address_matches("/Executor[1-3/Go", "/Executor[1-3/Go"));
+        assert!(!osc_address_matches("/Executor1/Go[", "/Executor1/Go"));
+    }
+
+    #[test]
+    fn dash_at_either_end_of_a_class_is_literal() {
+        assert!(class_contains("1-", '-'));
+        assert!(class_contains("1-", '1'));
+        assert!(!class_contains("1-", '0'));
+        assert!(class_contains("-1", '-'));
+        assert!(class_contains("!-1", '2'));
+        assert!(!class_contains("!-1", '-'));
+        assert!(class_contains("1-3", '2'));
+        assert!(!class_contains("1-3", '-'));
+    }
+
+    #[test]
+    fn alternation() {
+        assert!(osc_address_matches("/Executor{1,4}/Go", "/Executor1/Go"));
+        assert!(osc_address_matches("/Executor{1,4}/Go", "/Executor4/Go"));
+        assert!(!osc_address_matches("/Executor{1,4}/Go", "/Executor2/Go"));
+        assert!(osc_address_matches(
+            "/Executor1/{Go,GoBack}",
+            "/Executor1/Go"
+        ));
+        assert!(osc_address_matches(
+            "/Executor1/{Go,GoBack}",
+            "/Executor1/GoBack"
+        ));
+        assert!(!osc_address_matches(
+            "/Executor1/{Go,GoBack}",
+            "/Executor1/Dimmer"
+        ));
+        assert!(!osc_address_matches("/Executor{1,4/Go", "/Executor1/Go"));
+    }
+}
@@ -0,0 +1,73 @@
+//! Frame loop
+//!
+//! Everything that runs once per frame whether or not a window is open: OSC input
+//! and feedback, HTTP API requests, the audio engine, executor mixing and DMX output. The GUI calls
+//! [`tick`] from its update, headless mode from a timer in [`run_headless`].
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::dmx_output::{mix_executor_outputs, record_look_snapshot};
+use crate::http_api::{HttpApi, poll_http_api};
+use crate::osc::{OSCManager, handle_osc, send_feedback};
+use crate::show_file::ShowFile;
+use crate::ui::{ConsoleState, update_audio};
+
+/// Frame period without a window, close to the DMX refresh rate
+const HEADLESS_FRAME: Duration = Duration::from_millis(25);
+
+/// Runs one frame of the show
+pub fn tick(state: &mut ConsoleState) {
+    if let Some(osc_manager) = &mut state.osc_manager.1 {
+        handle_osc(osc_manager.get_osc(), state);
+    }
+    poll_http_api(state);
+    send_feedback(state);
+    update_audio(state);
+    mix_executor_outputs(state);
+    record_look_snapshot(state);
+}
+
+/// Plays a saved show without a window until Ctrl+C, remotely controlled over OSC
+/// when `osc_address` (ip:port) is given and over HTTP when `http_address` is
+pub async fn run_headless(
+    show_path: &Path,
+    osc_address: Option<String>,
+    http_address: Option<String>,
+) -> Result<(), String> {
+    let mut state = ConsoleState::default();
+    ShowFile::load_from_path(show_path)
+        .map_err(|e| e.to_string())?
+        .apply_to(&mut state);
+    state.show_path = Some(show_path.to_path_buf());
+    if let Some(address) = osc_address {
+        let manager = OSCManager::from(address.clone()).map_err(|e| e.to_string())?;
+        state.osc_manager.1 = Some(manager);
+        println!("Listening for OSC on {address}");
+    }
+    if let Some(address) = http_address {
+        let api = HttpApi::start(&address).map_err(|e| e.to_string())?;
+        println!("Serving the HTTP API on http://{}", api.address());
+        state.http_api.1 = Some(api);
+    }
+    if !state.dmx_serial_error.is_empty() {
+        println!("DMX output unavailable: {}", state.dmx_serial_error);
+    }
+    println!("Running {} headless, Ctrl+C to stop", show_path.display());
+
+    let mut frame = tokio::time::interval(HEADLESS_FRAME);
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = frame.tick() => tick(&mut state),
+            _ = &mut ctrl_c => break,
+        }
+    }
+
+    println!("Stopping");
+    if let Some(engine) = &state.audio_engine {
+        engine.stop_all();
+    }
+    Ok(())
+}
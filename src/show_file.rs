@@ -0,0 +1,226 @@
+//! Show file
+//!
+//! Saves and restores the programming of a show (patch, groups, cues, presets,
+//! audio playlist and masters) as JSON. Hardware connections and UI state are
+//! never part of it.
+//!
+//! Every file records the [`SHOW_FILE_VERSION`] it was written with. Older files
+//! are upgraded in [`migrate`] before being deserialized, files from a newer
+//! version are refused.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::dmx_types::{
+    AudioTrack, Executor, Fixture, FixtureGroup, FixtureTemplateLibrary, Preset,
+};
+use crate::ui::{ConsoleState, SUBMASTER_COUNT};
+
+/// Current show file schema.
+/// - 0: legacy layout without a `version` field, `submasters` or per-cue `fade_curve`
+/// - 1: adds `version`, `submasters`, the per-cue `fade_curve` and decimal cue numbers
+pub const SHOW_FILE_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum ShowFileError {
+    #[error("Could not access the show file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid show file: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error(
+        "Show file version {found} comes from a newer version of the app (supports up to {SHOW_FILE_VERSION})"
+    )]
+    TooNew { found: u32 },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ShowFile {
+    pub version: u32,
+    pub template_library: FixtureTemplateLibrary,
+    pub fixtures: Vec<Fixture>,
+    pub fixture_groups: Vec<FixtureGroup>,
+    pub executors: Vec<Executor>,
+    pub presets: Vec<Preset>,
+    pub audio_tracks: Vec<AudioTrack>,
+    pub master_dimmer: f32,
+    pub master_volume: f32,
+    pub submasters: Vec<f32>,
+}
+
+impl ShowFile {
+    pub fn from_state(state: &ConsoleState) -> Self {
+        Self {
+            version: SHOW_FILE_VERSION,
+            template_library: state.template_library.clone(),
+            fixtures: state.fixtures.clone(),
+            fixture_groups: state.fixture_groups.clone(),
+            executors: state.executors.clone(),
+            presets: state.presets.clone(),
+            audio_tracks: state.audio_tracks.clone(),
+            master_dimmer: state.master_dimmer,
+            master_volume: state.master_volume,
+            submasters: state.submasters.clone(),
+        }
+    }
+
+    /// Replaces the current show with this one, keeping the hardware connections
+    pub fn apply_to(self, state: &mut ConsoleState) {
+        state.new_show();
+        state.template_library = self.template_library;
+        state.fixtures = self.fixtures;
+        state.fixture_groups = self.fixture_groups;
+        state.executors = self.executors;
+        state.presets = self.presets;
+        state.audio_tracks = self.audio_tracks;
+        for track in &mut state.audio_tracks {
+            track.check_file();
+        }
+        state.master_dimmer = self.master_dimmer;
+        state.master_volume = self.master_volume;
+        state.submasters = self.submasters;
+        state.submasters.resize(SUBMASTER_COUNT, 1.0);
+    }
+
+    pub fn save_to_path(&self, path: impl AsRef<Path>) -> Result<(), ShowFileError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Reads a show file, upgrading it first if it was written by an older version
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, ShowFileError> {
+        let json = std::fs::read_to_string(path)?;
+        let show = migrate(serde_json::from_str(&json)?)?;
+        Ok(serde_json::from_value(show)?)
+    }
+}
+
+/// Upgrades a raw show file to [`SHOW_FILE_VERSION`], one version at a time,
+/// then sorts its cue lists
+fn migrate(mut show: Value) -> Result<Value, ShowFileError> {
+    let found = show.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if found > SHOW_FILE_VERSION {
+        return Err(ShowFileError::TooNew { found });
+    }
+    if found < 1 {
+        migrate_legacy(&mut show);
+    }
+    sort_cue_lists(&mut show);
+    show["version"] = json!(SHOW_FILE_VERSION);
+    Ok(show)
+}
+
+fn migrate_legacy(show: &mut Value) {
+    let Some(show) = show.as_object_mut() else {
+        return;
+    };
+    show.entry("submasters")
+        .or_insert_with(|| json!(vec![1.0; SUBMASTER_COUNT]));
+    let executors = show.get_mut("executors").and_then(Value::as_array_mut);
+    for executor in executors.into_iter().flatten() {
+        let cues = executor.get_mut("cue_list").and_then(Value::as_array_mut);
+        for cue in cues.into_iter().flatten() {
+            if let Some(cue) = cue.as_object_mut() {
+                cue.entry("fade_curve").or_insert_with(|| json!("Linear"));
+            }
+        }
+    }
+}
+
+/// Cue lists could be out of number order after moves, sort them keeping the current cue
+fn sort_cue_lists(show: &mut Value) {
+    let executors = show.get_mut("executors").and_then(Value::as_array_mut);
+    for executor in executors.into_iter().flatten() {
+        let current_cue = executor.get("current_cue").and_then(Value::as_f64);
+        let Some(cues) = executor.get_mut("cue_list").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        let id = |cue: &Value| cue.get("id").and_then(Value::as_f64).unwrap_or(0.0);
+        cues.sort_by(|a, b| id(a).total_cmp(&id(b)));
+        let current_idx = current_cue
+            .and_then(|current| cues.iter().position(|cue| id(cue) == current))
+            .unwrap_or(0);
+        executor["current_cue_index"] = json!(current_idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dmx_types::{CueNumber, FadeCurve};
+
+    /// Show in the legacy unversioned layout: no `version`, `submasters` or `fade_curve`,
+    /// integer cue ids and a cue list out of order
+    const LEGACY_SHOW: &str = r#"{
+        "template_library": { "templates": [], "next_id": 1 },
+        "fixtures": [],
+        "fixture_groups": [],
+        "executors": [
+            {
+                "id": 0,
+                "current_cue": 1,
+                "current_cue_index": 1,
+                "cue_list": [
+                    { "id": 2, "name": "Verse", "fade_time": 1.5, "delay": 0.0, "levels": [0, 255] },
+                    { "id": 1, "name": "Intro", "fade_time": 0.0, "delay": 0.0, "levels": [255, 0] }
+                ]
+            }
+        ],
+        "presets": [],
+        "audio_tracks": [],
+        "master_dimmer": 0.8,
+        "master_volume": 1.0
+    }"#;
+
+    #[test]
+    fn legacy_show_loads() {
+        let path =
+            std::env::temp_dir().join(format!("q-gui-show-legacy-{}.json", std::process::id()));
+        std::fs::write(&path, LEGACY_SHOW).unwrap();
+        let show = ShowFile::load_from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        let show = show.unwrap();
+
+        assert_eq!(show.version, SHOW_FILE_VERSION);
+        assert_eq!(show.master_dimmer, 0.8);
+        assert_eq!(show.submasters, vec![1.0; SUBMASTER_COUNT]);
+        let exec = &show.executors[0];
+        let ids: Vec<CueNumber> = exec.cue_list.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [CueNumber::FIRST, CueNumber::whole(2).unwrap()]);
+        assert!(
+            exec.cue_list
+                .iter()
+                .all(|c| c.fade_curve == FadeCurve::Linear)
+        );
+        // Cue 1 was current before the sort and still is
+        assert_eq!(exec.current_cue_index, 0);
+        assert_eq!(exec.cue_list[0].name, "Intro");
+    }
+
+    #[test]
+    fn legacy_show_migrates_to_current_version() {
+        let show = migrate(serde_json::from_str(LEGACY_SHOW).unwrap()).unwrap();
+        assert_eq!(show["version"], json!(SHOW_FILE_VERSION));
+        assert_eq!(show["submasters"], json!(vec![1.0; SUBMASTER_COUNT]));
+        assert_eq!(
+            show["executors"][0]["cue_list"][0]["fade_curve"],
+            json!("Linear")
+        );
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        let found = SHOW_FILE_VERSION + 1;
+        let error = migrate(json!({ "version": found })).unwrap_err();
+        assert!(matches!(error, ShowFileError::TooNew { found: f } if f == found));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Show file version {found} comes from a newer version of the app (supports up to {SHOW_FILE_VERSION})"
+            )
+        );
+    }
+}
@@ -1,12 +1,22 @@
-use crate::console::{ConsoleCommand, execute_console_command};
+use crate::audio::MAX_MASTER_VOLUME;
+use crate::console::{
+    ConsoleCommand, execute_console_command, move_cues, set_buffer_value, store_buffer_to_executor,
+};
+use crate::dmx_output::{
+    FreezeMode, LookSnapshot, MAX_MOVE_TIME, MAX_OOPS_LOOKBACK, MoveRamp, oops, reconnect_dmx,
+};
 use crate::dmx_types::{
-    AudioAction, AudioTrack, ChannelType, Cue, DMX_CHANNELS, DMXBufferValue, Executor, Fixture,
-    FixtureGroup, FixtureTemplateLibrary,
+    AudioAction, AudioTrack, CCT_COOLEST, CCT_WARMEST, ChannelType, Color, CueNumber, DMX_CHANNELS,
+    DMXBufferValue, DimmerCurve, Executor, FadeCurve, Fixture, FixtureFilter, FixtureGroup,
+    FixtureTemplate, FixtureTemplateLibrary, FollowChain, Preset, PresetKind,
 };
+use crate::osc::OSCTarget;
+use crate::show_file::ShowFile;
 use egui::epaint::ColorMode;
 use egui::{Color32, DragValue, Key, RichText, ScrollArea, TextEdit, Vec2};
-use open_dmx::check_valid_channel;
-#[derive(PartialEq, Default, Clone)]
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+#[derive(PartialEq, Default, Clone, Serialize, Deserialize)]
 pub enum Tab {
     #[default]
     DmxConsole = 0,
@@ -15,22 +25,144 @@ pub enum Tab {
     Show = 3,
 }
 
-#[derive(PartialEq, Default)]
+#[derive(PartialEq, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum DmxSubTab {
     #[default]
     Executor,
     Fixtures,
 }
 
-#[derive(PartialEq, Default)]
+#[derive(PartialEq, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum FixturesTab {
     #[default]
     Creation,
     Grouping,
     Editing,
     List,
+    Presets,
+}
+
+/// Which panels the DMX console shows, to fit smaller screens
+#[derive(PartialEq, Default, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum LayoutPreset {
+    /// Every panel, for a 1280x800 or larger screen
+    #[default]
+    Full,
+    /// Hides the command palette and history
+    Compact,
+}
+
+impl LayoutPreset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LayoutPreset::Full => "Full",
+            LayoutPreset::Compact => "Compact",
+        }
+    }
+
+    /// Smallest window the preset is designed for
+    pub fn min_window_size(&self) -> Vec2 {
+        match self {
+            LayoutPreset::Full => Vec2::new(1280.0, 800.0),
+            LayoutPreset::Compact => Vec2::new(960.0, 600.0),
+        }
+    }
+}
+
+/// Window layout preferences, kept across launches
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LayoutSettings {
+    pub preset: LayoutPreset,
+    /// Minimum window size, starts at the preset's and can be lowered further
+    pub min_window_size: Vec2,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        Self {
+            preset: LayoutPreset::Full,
+            min_window_size: LayoutPreset::Full.min_window_size(),
+        }
+    }
+}
+
+/// Tabs open when the app was closed, restored on the next launch
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TabSelection {
+    pub tab: Tab,
+    pub dmx_sub_tab: DmxSubTab,
+    pub fixtures_tab: FixturesTab,
+}
+
+/// Color scheme of the whole UI
+#[derive(PartialEq, Default, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Theme {
+    /// For dark venues
+    #[default]
+    Dark,
+    /// For daytime setup
+    Light,
+}
+
+impl Theme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+
+    pub fn egui_theme(&self) -> egui::Theme {
+        match self {
+            Theme::Dark => egui::Theme::Dark,
+            Theme::Light => egui::Theme::Light,
+        }
+    }
+}
+
+/// Fills of the command buttons. The accent marks the active edit mode.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ButtonPalette {
+    pub accent: Color32,
+    pub store: Color32,
+    pub edit: Color32,
+    pub delete: Color32,
+    pub label: Color32,
+    /// Copy and Move
+    pub copy_move: Color32,
+    /// Buttons with nothing to act on
+    pub idle: Color32,
+}
+
+impl Default for ButtonPalette {
+    fn default() -> Self {
+        Self {
+            accent: Color32::from_rgb(255, 165, 0),
+            store: Color32::from_rgb(0, 100, 200),
+            edit: Color32::from_rgb(200, 100, 0),
+            delete: Color32::from_rgb(150, 50, 50),
+            label: Color32::from_rgb(50, 100, 200),
+            copy_move: Color32::from_rgb(200, 100, 0),
+            idle: Color32::GRAY,
+        }
+    }
+}
+
+/// Theme and button colors, kept across launches
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub theme: Theme,
+    pub palette: ButtonPalette,
 }
 
+/// Two fixture IDs and the DMX channels they share (1-based, end exclusive)
+pub type PatchConflict = (u32, u32, std::ops::Range<usize>);
+
+/// Window width below which the executor side panel narrows and scrolls
+const NARROW_WINDOW_WIDTH: f32 = 1100.0;
+
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub enum EditingState {
     #[default]
@@ -85,8 +217,28 @@ impl EditingState {
     pub fn is_store(&self) -> bool {
         *self == Self::Store
     }
+    /// Whether this state modifies an executor's programming (refused on locked executors)
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Self::Store | Self::Edit | Self::Delete | Self::Copy | Self::Move
+        )
+    }
+}
+
+/// What the path picked in a pending file dialog will be used for
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FileDialogPurpose {
+    AddAudioTrack,
+    OpenShow,
+    SaveShow,
+    LoadPlaylist,
+    SavePlaylist,
 }
 
+/// Number of submaster faders available to group executors
+pub const SUBMASTER_COUNT: usize = 4;
+
 /// Main application state containing all DMX console data
 pub struct ConsoleState {
     pub edit_state: EditingState,
@@ -94,24 +246,58 @@ pub struct ConsoleState {
     pub command_input: String,
     /// Error message to display from last command (if any)
     pub command_error: Option<String>,
+    /// Give keyboard focus back to the command line on the next frame
+    pub focus_command_input: bool,
     /// History of successfully executed commands
     pub command_history: Vec<ConsoleCommand>,
     /// Final mixed DMX output channels (512 channels) sent to hardware
     pub channels: Vec<u8>,
     /// Buffer containing pending DMX values before storing to a cue
     pub buffer: Vec<DMXBufferValue>,
-    /// Index of cue being labeled (if any)
-    pub labeling_cue: Option<u32>,
+    /// Number of the cue being labeled (if any)
+    pub labeling_cue: Option<CueNumber>,
+    /// Number typed for the next stored cue, empty for the next whole number
+    pub store_cue_number: String,
+    /// Cues ticked in the cue list window (executor index, cue), for batch moves
+    pub selected_cues: std::collections::BTreeSet<(usize, CueNumber)>,
+    /// Executor (1-based) the selected cues are moved to
+    pub batch_move_target: u32,
     /// Temporary name buffer for labeling
     pub label_buffer: String,
+    /// ID of the fixture being renamed in the Existing Fixtures list, uses `label_buffer`
+    pub renaming_fixture: Option<u32>,
     /// Index of executor currently being edited (if any)
     pub editing_executor: Option<usize>,
     /// Index of executor pending delete confirmation (if any)
     pub delete_confirm_executor: Option<usize>,
+    /// Whether the New Show confirmation is open
+    pub new_show_confirm: bool,
+    /// Layout preset and minimum window size
+    pub layout: LayoutSettings,
+    /// Minimum window size last sent to the viewport
+    pub applied_min_window_size: Option<Vec2>,
+    /// Dark/light theme and button colors
+    pub theme: ThemeSettings,
+    /// Theme last applied to the context
+    pub applied_theme: Option<Theme>,
+    /// Overlapping fixture pairs, with the hash of the patch they were computed from
+    pub patch_conflicts: (Option<u64>, Vec<PatchConflict>),
+    /// Cached cue thumbnails by (executor index, cue id), with the hash of the data they were drawn from
+    pub cue_thumbnails: std::collections::HashMap<(usize, CueNumber), (u64, Vec<Color32>)>,
     /// Whether the buffer popup window is visible
     pub show_buffer: bool,
     /// Whether the channels popup window is visible
     pub show_channels: bool,
+    /// Whether the DMX monitor window is visible
+    pub show_dmx_monitor: bool,
+    /// Outline channels in the monitor for a moment after their value changes
+    pub monitor_highlight_changes: bool,
+    /// Show which fixture owns each channel in the monitor
+    pub monitor_show_owners: bool,
+    /// Output values seen by the monitor on the previous frame
+    pub monitor_last_channels: Vec<u8>,
+    /// When each channel last changed, for the monitor highlight
+    pub monitor_changed_at: Vec<Option<Instant>>,
     /// List of executors (playback faders with cue lists)
     pub executors: Vec<Executor>,
     /// Currently selected main tab
@@ -134,6 +320,8 @@ pub struct ConsoleState {
     pub selected_fixture_ids: Vec<u32>,
     /// ID of currently selected fixture group (if any)
     pub selected_group_id: Option<u32>,
+    /// Level of the group intensity fader, with the group it belongs to
+    pub group_intensity: Option<(u32, u8)>,
     /// ID of currently selected fixture for editing (in List tab)
     pub selected_fixture_id: Option<u32>,
     /// Input field for new fixture name
@@ -142,12 +330,32 @@ pub struct ConsoleState {
     pub new_fixture_start_channel: String,
     /// Input field for new group name
     pub new_group_name: String,
-    /// Grid index for new group
-    pub new_group_grid_index: Option<usize>,
+    /// Grid position typed for the new group (1-based, empty for none)
+    pub new_group_grid_input: String,
     /// Error message for fixture operations
     pub fixture_error: Option<String>,
+    /// Warning left by the last fixture mode change, e.g. an overlapping footprint
+    pub fixture_warning: Option<String>,
+    /// Stored color/position/beam presets
+    pub presets: Vec<Preset>,
+    /// Input field for new preset name
+    pub new_preset_name: String,
+    /// Kind of the next preset to store
+    pub new_preset_kind: PresetKind,
     /// Master dimmer fader (0.0 to 1.0)
     pub master_dimmer: f32,
+    /// Submaster faders (0.0 to 1.0), scaling the executors assigned to them
+    pub submasters: Vec<f32>,
+    /// Looks recorded every second for the Oops button (oldest first)
+    pub look_history: std::collections::VecDeque<LookSnapshot>,
+    /// How many seconds back the Oops button restores
+    pub oops_lookback: u32,
+    /// Value written to every shutter/strobe channel by the Strobe button
+    pub strobe_value: u8,
+    /// Seconds pan/tilt changes from the buffer take to reach their position, 0 is instant
+    pub move_time: f32,
+    /// Pan/tilt ramps in progress, by fixture id
+    pub move_ramps: std::collections::HashMap<u32, MoveRamp>,
     /// Audio tracks
     pub audio_tracks: Vec<AudioTrack>,
     /// Master volume for audio (0.0 to 1.0)
@@ -164,10 +372,37 @@ pub struct ConsoleState {
     pub dmx_connected: bool,
     /// Last error message from DMX serial operations
     pub dmx_serial_error: String,
+    /// Number of consecutive failed DMX agent checks
+    pub dmx_failed_checks: u32,
+    /// Holds the DMX output at its current values, skipping the mix
+    pub frozen: bool,
+    /// Whether a frozen output keeps being sent
+    pub freeze_mode: FreezeMode,
     /// The OSC Manager
     pub osc_manager: (String, Option<crate::osc::OSCManager>),
+    /// Error from the last OSC connect attempt
+    pub osc_error: Option<String>,
+    /// The OSC feedback target address being typed, and the active sender
+    pub osc_feedback: (String, Option<crate::osc::OSCFeedback>),
     /// Binding osc address to application actions
     pub osc_address_manager: crate::osc::OSCNaming,
+    /// The HTTP API bind address being typed, and the running server
+    pub http_api: (String, Option<crate::http_api::HttpApi>),
+    /// Error from the last HTTP API start attempt
+    pub http_api_error: Option<String>,
+    /// When each control was last acted on by an OSC message, to flash it
+    pub osc_touched: std::collections::HashMap<crate::osc::OSCTarget, Instant>,
+    /// File the show was last opened from or saved to
+    pub show_path: Option<std::path::PathBuf>,
+    /// Error from the last show open/save
+    pub show_file_error: Option<String>,
+    /// Last audio problem: playlist load/save error, missing files or failed playback
+    pub audio_error: Option<String>,
+    /// File dialog currently open, with the channel its result arrives on
+    pub file_dialog: Option<(
+        FileDialogPurpose,
+        crossbeam_channel::Receiver<Option<std::path::PathBuf>>,
+    )>,
 }
 
 impl Default for ConsoleState {
@@ -177,18 +412,52 @@ impl Default for ConsoleState {
         } else {
             "/dev/ttyUSB0"
         };
+        Self {
+            audio_engine: crate::audio::AudioEngine::new().ok(),
+            dmx_serial: {
+                let mut serial = open_dmx::DMXSerial::open(port).ok();
+                if let Some(dmx) = &mut serial {
+                    dmx.set_sync();
+                }
+                serial
+            },
+            ..Self::without_hardware()
+        }
+    }
+}
+
+impl ConsoleState {
+    /// An empty show with no audio engine or DMX port opened
+    pub(crate) fn without_hardware() -> Self {
         Self {
             command_input: Default::default(),
             command_error: Default::default(),
+            focus_command_input: Default::default(),
             command_history: Default::default(),
             channels: vec![0; DMX_CHANNELS],
             buffer: Default::default(),
             labeling_cue: Default::default(),
+            store_cue_number: String::new(),
+            selected_cues: Default::default(),
+            batch_move_target: 1,
             label_buffer: Default::default(),
+            renaming_fixture: Default::default(),
             editing_executor: Default::default(),
             delete_confirm_executor: Default::default(),
+            new_show_confirm: Default::default(),
+            layout: Default::default(),
+            applied_min_window_size: None,
+            theme: Default::default(),
+            applied_theme: None,
+            cue_thumbnails: Default::default(),
+            patch_conflicts: Default::default(),
             show_buffer: Default::default(),
             show_channels: Default::default(),
+            show_dmx_monitor: Default::default(),
+            monitor_highlight_changes: true,
+            monitor_show_owners: Default::default(),
+            monitor_last_channels: vec![0; DMX_CHANNELS],
+            monitor_changed_at: vec![None; DMX_CHANNELS],
             executors: (0..10).map(Executor::new).collect(),
             selected_tab: Default::default(),
             dmx_sub_tab: Default::default(),
@@ -200,35 +469,420 @@ impl Default for ConsoleState {
             selected_mode_index: Default::default(),
             selected_fixture_ids: Default::default(),
             selected_group_id: None,
+            group_intensity: None,
             selected_fixture_id: None,
             new_fixture_name: Default::default(),
             new_fixture_start_channel: Default::default(),
             new_group_name: Default::default(),
-            new_group_grid_index: None,
+            new_group_grid_input: Default::default(),
             fixture_error: Default::default(),
+            fixture_warning: Default::default(),
+            presets: Default::default(),
+            new_preset_name: Default::default(),
+            new_preset_kind: PresetKind::Color,
             master_dimmer: 1.0,
+            submasters: vec![1.0; SUBMASTER_COUNT],
+            look_history: Default::default(),
+            oops_lookback: 5,
+            move_time: 0.0,
+            move_ramps: Default::default(),
+            strobe_value: 200,
             audio_tracks: Default::default(),
             master_volume: 1.0,
             selected_audio_track_id: Default::default(),
             audio_index: Default::default(),
-            audio_engine: crate::audio::AudioEngine::new().ok(),
-            dmx_serial: {
-                let mut serial = open_dmx::DMXSerial::open(port).ok();
-                if let Some(dmx) = &mut serial {
-                    dmx.set_sync();
-                }
-                serial
-            },
+            audio_engine: None,
+            dmx_serial: None,
             dmx_connected: Default::default(),
             dmx_serial_error: Default::default(),
+            dmx_failed_checks: Default::default(),
+            frozen: Default::default(),
+            freeze_mode: Default::default(),
             edit_state: Default::default(),
             osc_manager: (Default::default(), Default::default()),
+            osc_error: Default::default(),
+            osc_feedback: (Default::default(), Default::default()),
             osc_address_manager: Default::default(),
+            http_api: (Default::default(), Default::default()),
+            http_api_error: Default::default(),
+            osc_touched: Default::default(),
+            show_path: None,
+            show_file_error: None,
+            audio_error: None,
+            file_dialog: None,
+        }
+    }
+
+    /// Resets the console to an empty show, keeping the DMX port, audio engine and OSC listener
+    pub fn new_show(&mut self) {
+        if let Some(engine) = &self.audio_engine {
+            engine.stop_all();
+        }
+        *self = Self {
+            audio_engine: self.audio_engine.take(),
+            dmx_serial: self.dmx_serial.take(),
+            dmx_connected: self.dmx_connected,
+            dmx_serial_error: std::mem::take(&mut self.dmx_serial_error),
+            dmx_failed_checks: self.dmx_failed_checks,
+            freeze_mode: self.freeze_mode,
+            osc_manager: std::mem::take(&mut self.osc_manager),
+            osc_feedback: std::mem::take(&mut self.osc_feedback),
+            osc_address_manager: self.osc_address_manager.clone(),
+            http_api: std::mem::take(&mut self.http_api),
+            // Keep the last output so the next mix sees the change and blacks out the rig
+            channels: std::mem::take(&mut self.channels),
+            selected_tab: self.selected_tab.clone(),
+            dmx_sub_tab: self.dmx_sub_tab,
+            fixtures_tab: self.fixtures_tab,
+            layout: self.layout.clone(),
+            applied_min_window_size: self.applied_min_window_size,
+            theme: self.theme.clone(),
+            applied_theme: self.applied_theme,
+            ..Self::without_hardware()
+        };
+    }
+
+    pub fn tab_selection(&self) -> TabSelection {
+        TabSelection {
+            tab: self.selected_tab.clone(),
+            dmx_sub_tab: self.dmx_sub_tab,
+            fixtures_tab: self.fixtures_tab,
+        }
+    }
+
+    pub fn restore_tab_selection(&mut self, tabs: TabSelection) {
+        self.selected_tab = tabs.tab;
+        self.dmx_sub_tab = tabs.dmx_sub_tab;
+        self.fixtures_tab = tabs.fixtures_tab;
+    }
+
+    /// DMX channels (1-based, end exclusive) occupied by a fixture in its current mode
+    pub fn fixture_footprint(&self, fixture: &Fixture) -> std::ops::Range<usize> {
+        let num_channels = self
+            .template_library
+            .get_template(fixture.template_id)
+            .and_then(|t| t.get_mode(fixture.mode_index))
+            .map(|m| m.channels.len())
+            .unwrap_or(0);
+        fixture.start_channel..fixture.start_channel + num_channels
+    }
+
+    /// Fixtures whose footprint shares channels with `footprint` (1-based, end exclusive)
+    pub fn fixtures_overlapping(&self, footprint: &std::ops::Range<usize>) -> Vec<&Fixture> {
+        self.fixtures
+            .iter()
+            .filter(|f| {
+                let other = self.fixture_footprint(f);
+                other.start < footprint.end && footprint.start < other.end
+            })
+            .collect()
+    }
+
+    /// Switches a fixture to another mode of its template.
+    /// Channels the fixture no longer uses, and no other fixture does, are dropped
+    /// from the buffer and cleared in every cue, so they stop being output.
+    /// Returns a warning if the new footprint overlaps another fixture.
+    pub fn set_fixture_mode(&mut self, fixture_id: u32, mode_index: usize) -> Option<String> {
+        let idx = self.fixtures.iter().position(|f| f.id == fixture_id)?;
+        let old_footprint = self.fixture_footprint(&self.fixtures[idx]);
+        self.fixtures[idx].mode_index = mode_index;
+        let new_footprint = self.fixture_footprint(&self.fixtures[idx]);
+
+        // Clear the channels freed by a smaller footprint
+        let freed: Vec<usize> = old_footprint
+            .filter(|chan| !new_footprint.contains(chan))
+            .filter(|chan| {
+                !self
+                    .fixtures
+                    .iter()
+                    .any(|f| f.id != fixture_id && self.fixture_footprint(f).contains(chan))
+            })
+            .collect();
+        self.buffer.retain(|v| !freed.contains(&v.chan));
+        for exec in &mut self.executors {
+            let cue_levels = exec.cue_list.iter_mut().map(|cue| &mut cue.levels);
+            for levels in cue_levels.chain([&mut exec.stored_channels]) {
+                for chan in &freed {
+                    if let Some(level) = levels.get_mut(chan.saturating_sub(1)) {
+                        *level = 0;
+                    }
+                }
+            }
+        }
+
+        if new_footprint.end > DMX_CHANNELS + 1 {
+            return Some(format!(
+                "Fixture {fixture_id} now ends at channel {}, beyond channel {DMX_CHANNELS}",
+                new_footprint.end - 1
+            ));
+        }
+        let overlapping: Vec<String> = self
+            .fixtures
+            .iter()
+            .filter(|f| f.id != fixture_id)
+            .filter(|f| {
+                let other = self.fixture_footprint(f);
+                other.start < new_footprint.end && new_footprint.start < other.end
+            })
+            .map(|f| f.name.clone())
+            .collect();
+        if overlapping.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "Channels {} to {} now overlap with: {}",
+                new_footprint.start,
+                new_footprint.end - 1,
+                overlapping.join(", ")
+            ))
+        }
+    }
+}
+
+/// Most fixtures drawn in a cue thumbnail
+const THUMBNAIL_CELLS: usize = 16;
+
+impl ConsoleState {
+    /// Thumbnails of an executor's cues, in cue list order. Each is one swatch per
+    /// fixture lit by the cue. Rebuilt only when the cue levels or the patch change.
+    pub fn cue_thumbnails(&mut self, exec_idx: usize) -> Vec<Vec<Color32>> {
+        use std::hash::{Hash, Hasher};
+        let Some(executor) = self.executors.get(exec_idx) else {
+            return Vec::new();
+        };
+        let patch_hasher = self.patch_hasher();
+        let mut thumbnails = Vec::with_capacity(executor.cue_list.len());
+        for cue in &executor.cue_list {
+            let mut hasher = patch_hasher.clone();
+            cue.levels.hash(&mut hasher);
+            let hash = hasher.finish();
+            let key = (exec_idx, cue.id);
+            match self.cue_thumbnails.get(&key) {
+                Some((cached, thumbnail)) if *cached == hash => thumbnails.push(thumbnail.clone()),
+                _ => {
+                    let thumbnail = self.look_swatches(&cue.levels);
+                    self.cue_thumbnails.insert(key, (hash, thumbnail.clone()));
+                    thumbnails.push(thumbnail);
+                }
+            }
+        }
+        // Forget the cues deleted or renumbered since
+        self.cue_thumbnails.retain(|&(idx, number), _| {
+            idx != exec_idx
+                || executor
+                    .cue_list
+                    .binary_search_by_key(&number, |cue| cue.id)
+                    .is_ok()
+        });
+        thumbnails
+    }
+
+    /// Hasher fed with the patch layout, to tell when fixtures moved, changed mode or were added
+    fn patch_hasher(&self) -> std::hash::DefaultHasher {
+        use std::hash::Hash;
+        let mut hasher = std::hash::DefaultHasher::new();
+        for fixture in &self.fixtures {
+            (
+                fixture.id,
+                fixture.start_channel,
+                fixture.template_id,
+                fixture.mode_index,
+            )
+                .hash(&mut hasher);
+        }
+        hasher
+    }
+
+    /// Every pair of fixtures sharing DMX channels, with the shared range (1-based, end exclusive)
+    pub fn patch_conflicts(&self) -> Vec<PatchConflict> {
+        let footprints: Vec<_> = self
+            .fixtures
+            .iter()
+            .map(|f| (f.id, self.fixture_footprint(f)))
+            .collect();
+        let mut conflicts = Vec::new();
+        for (i, (id_a, a)) in footprints.iter().enumerate() {
+            for (id_b, b) in &footprints[i + 1..] {
+                let overlap = a.start.max(b.start)..a.end.min(b.end);
+                if !overlap.is_empty() {
+                    conflicts.push((*id_a, *id_b, overlap));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Patch conflicts, recomputed only when the fixtures changed since the last call
+    pub fn cached_patch_conflicts(&mut self) -> &[PatchConflict] {
+        use std::hash::Hasher;
+        let hash = self.patch_hasher().finish();
+        if self.patch_conflicts.0 != Some(hash) {
+            self.patch_conflicts = (Some(hash), self.patch_conflicts());
+        }
+        &self.patch_conflicts.1
+    }
+
+    /// Color of each fixture lit in `levels`, in patch order
+    fn look_swatches(&self, levels: &[u8]) -> Vec<Color32> {
+        let mut swatches = Vec::new();
+        for fixture in &self.fixtures {
+            let Some(mode) = self
+                .template_library
+                .get_template(fixture.template_id)
+                .and_then(|t| t.get_mode(fixture.mode_index))
+            else {
+                continue;
+            };
+            let (mut r, mut g, mut b, mut w) = (0u16, 0u16, 0u16, 0u16);
+            let (mut intensity, mut has_color) = (None, false);
+            for chan_def in &mode.channels {
+                let idx = (fixture.start_channel + chan_def.offset as usize).saturating_sub(1);
+                let value = levels.get(idx).copied().unwrap_or_default() as u16;
+                match chan_def.channel_type {
+                    ChannelType::Intensity => intensity = Some(value),
+                    ChannelType::Red => (r, has_color) = (value, true),
+                    ChannelType::Green => (g, has_color) = (value, true),
+                    ChannelType::Blue => (b, has_color) = (value, true),
+                    ChannelType::White => (w, has_color) = (value, true),
+                    _ => {}
+                }
+            }
+            let (r, g, b) = if has_color {
+                let scale = intensity.unwrap_or(255);
+                let mix = |c: u16| ((c + w).min(255) * scale / 255) as u8;
+                (mix(r), mix(g), mix(b))
+            } else {
+                let level = intensity.unwrap_or_default() as u8;
+                (level, level, level)
+            };
+            if (r, g, b) != (0, 0, 0) {
+                swatches.push(Color32::from_rgb(r, g, b));
+                if swatches.len() == THUMBNAIL_CELLS {
+                    break;
+                }
+            }
+        }
+        swatches
+    }
+
+    /// Sets the intensity of every fixture in a group, on the fixtures and in the buffer
+    pub fn set_group_intensity(&mut self, group_id: u32, value: u8) {
+        let Some(group) = self.fixture_groups.iter().find(|g| g.id == group_id) else {
+            return;
+        };
+        for fixture in self
+            .fixtures
+            .iter_mut()
+            .filter(|f| group.fixture_ids.contains(&f.id))
+        {
+            let Some(mode) = self
+                .template_library
+                .get_template(fixture.template_id)
+                .and_then(|t| t.get_mode(fixture.mode_index))
+            else {
+                continue;
+            };
+            for chan_def in mode
+                .channels
+                .iter()
+                .filter(|c| c.channel_type == ChannelType::Intensity)
+            {
+                fixture.set_channel_value(chan_def, value);
+                set_buffer_value(
+                    &mut self.buffer,
+                    fixture.start_channel + chan_def.offset as usize,
+                    value,
+                );
+            }
+        }
+    }
+
+    /// Selects the fixtures whose mode matches `filter`, replacing the selection unless `add`
+    pub fn select_fixtures_matching(&mut self, filter: FixtureFilter, add: bool) {
+        if !add {
+            self.selected_fixture_ids.clear();
+        }
+        for fixture in &self.fixtures {
+            let matches = self
+                .template_library
+                .get_template(fixture.template_id)
+                .and_then(|t| t.get_mode(fixture.mode_index))
+                .is_some_and(|mode| filter.matches(mode));
+            if matches && !self.selected_fixture_ids.contains(&fixture.id) {
+                self.selected_fixture_ids.push(fixture.id);
+            }
+        }
+    }
+
+    /// Records the selected fixtures' current values of the given kind as a new preset.
+    /// The first selected fixture having a channel type provides its value.
+    pub fn store_preset(&mut self, kind: PresetKind, name: String) -> Result<(), String> {
+        let mut values = std::collections::HashMap::new();
+        for fixture in self
+            .fixtures
+            .iter()
+            .filter(|f| self.selected_fixture_ids.contains(&f.id))
+        {
+            if let Some(template) = self.template_library.get_template(fixture.template_id) {
+                for (chan_type, buf) in fixture.get_fixture_as_buffer(template) {
+                    if kind.includes(chan_type) {
+                        values.entry(chan_type).or_insert(buf.dmx);
+                    }
+                }
+            }
+        }
+        if values.is_empty() {
+            return Err(format!(
+                "No {} channels on the selected fixtures",
+                kind.name()
+            ));
+        }
+        let id = self.presets.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+        self.presets.push(Preset {
+            id,
+            kind,
+            name,
+            values,
+        });
+        Ok(())
+    }
+
+    /// Recalls a preset onto the selected fixtures, writing through the buffer.
+    /// Channels outside the preset's kind are left alone.
+    pub fn apply_preset(&mut self, preset_id: u32) {
+        let Some(preset) = self.presets.iter().find(|p| p.id == preset_id) else {
+            return;
+        };
+        for fixture in self
+            .fixtures
+            .iter_mut()
+            .filter(|f| self.selected_fixture_ids.contains(&f.id))
+        {
+            let Some(mode) = self
+                .template_library
+                .get_template(fixture.template_id)
+                .and_then(|t| t.get_mode(fixture.mode_index))
+            else {
+                continue;
+            };
+            for chan_def in &mode.channels {
+                if let Some(&value) = preset.values.get(&chan_def.channel_type) {
+                    fixture.set_channel_value(chan_def, value);
+                    set_buffer_value(
+                        &mut self.buffer,
+                        fixture.start_channel + chan_def.offset as usize,
+                        value,
+                    );
+                }
+            }
         }
     }
 }
 
 pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    show_submaster_faders(ui, state);
+    ui.separator();
+
     ui.heading("Executors");
     ui.separator();
 
@@ -250,21 +904,85 @@ pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState)
 
                 let exec = &mut state.executors[exec_idx];
                 let has_cues = !exec.cue_list.is_empty();
+                let osc_target = OSCTarget::Executor(exec.id);
+                let mut store_requested = false;
 
-                ui.vertical(|ui| {
-                    ui.label(RichText::new(format!("Exec {}", exec_idx + 1)).strong());
+                let column = ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("Exec {}", exec_idx + 1)).strong());
+                        let lock_icon = if exec.locked { "🔒" } else { "🔓" };
+                        if ui
+                            .small_button(lock_icon)
+                            .on_hover_text("Lock this executor against Store/Edit/Delete/Copy/Move")
+                            .clicked()
+                        {
+                            exec.locked = !exec.locked;
+                        }
+                        let wrap_icon = if exec.wrap { "🔁" } else { "⏹" };
+                        if ui
+                            .small_button(wrap_icon)
+                            .on_hover_text(if exec.wrap {
+                                "GO after the last cue starts over at the first"
+                            } else {
+                                "GO stops at the last cue"
+                            })
+                            .clicked()
+                        {
+                            exec.wrap = !exec.wrap;
+                        }
+                    });
 
-                    let _slider_response = ui.add_enabled(
+                    let slider_response = ui.add_enabled(
                         has_cues,
                         egui::Slider::new(&mut exec.fader_level, 0.0..=1.0)
                             .vertical()
                             .text(""),
                     );
+                    if has_cues {
+                        scroll_fader(ui, &slider_response, &mut exec.fader_level, 1.0);
+                    }
+                    if exec.is_fading {
+                        ui.add(
+                            egui::ProgressBar::new(exec.fade_progress)
+                                .desired_width(fader_width)
+                                .desired_height(4.0),
+                        );
+                    }
 
                     if !has_cues {
                         ui.label(RichText::new("(No cues)").weak().small());
+                    } else if exec.hit_end_of_list(1.5) {
+                        ui.label(RichText::new("End of list").color(Color32::ORANGE).small());
                     }
 
+                    egui::ComboBox::from_id_salt(("exec_submaster", exec_idx))
+                        .width(fader_width)
+                        .selected_text(match exec.submaster {
+                            Some(sub) => format!("Sub {}", sub + 1),
+                            None => "Sub -".to_string(),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut exec.submaster, None, "None");
+                            for sub in 0..SUBMASTER_COUNT as u8 {
+                                ui.selectable_value(
+                                    &mut exec.submaster,
+                                    Some(sub),
+                                    format!("Sub {}", sub + 1),
+                                );
+                            }
+                        });
+
+                    egui::ComboBox::from_id_salt(("exec_dimmer_curve", exec_idx))
+                        .width(fader_width)
+                        .selected_text(exec.dimmer_curve.name())
+                        .show_ui(ui, |ui| {
+                            for curve in DimmerCurve::ALL {
+                                ui.selectable_value(&mut exec.dimmer_curve, curve, curve.name());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Fader response curve");
+
                     if ui
                         .add_enabled(
                             has_cues,
@@ -273,30 +991,23 @@ pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState)
                                 .speed(0.01)
                                 .suffix("%"),
                         )
-                        .clicked()
+                        .changed()
+                        && exec.fader_level > 0.0
+                        && exec.current_cue.is_none()
                     {
-                        exec.current_cue = if let Some(id) = exec.current_cue {
-                            Some(id)
-                        } else {
-                            Some(1)
-                        };
+                        // Raising the fader plays the cue it points at, not always the first
+                        exec.current_cue = exec.cue_list.get(exec.current_cue_index).map(|c| c.id);
                     }
 
                     let button_size = Vec2::new(fader_width, 30.0);
                     let go_button = egui::Button::new("GO").fill(Color32::DARK_GREEN);
                     if ui.add_sized(button_size, go_button).clicked() {
-                        if state.edit_state.is_store() {
-                            let mut levels = vec![0; DMX_CHANNELS];
-                            for val in &state.buffer {
-                                if check_valid_channel(val.chan).is_ok() {
-                                    levels[val.chan.saturating_sub(1)] = val.dmx;
-                                }
-                            }
-                            let mut new_cue =
-                                Cue::new(exec.cue_list.len().saturating_add(1) as u32);
-                            new_cue.levels = levels;
-                            exec.cue_list.push(new_cue);
-                            state.edit_state.reset();
+                        if exec.locked && state.edit_state.is_destructive() {
+                            state.command_error =
+                                Some(format!("Executor {} is locked", exec_idx + 1));
+                        } else if state.edit_state.is_store() {
+                            // Stored once the executor is no longer borrowed
+                            store_requested = true;
                         } else if state.edit_state.is_edit() {
                             state.editing_executor = Some(exec_idx);
                         } else if state.edit_state.is_delete() {
@@ -310,7 +1021,10 @@ pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState)
 
                     let go_back_button = egui::Button::new("BACK").fill(Color32::DARK_BLUE);
                     if ui.add_sized(button_size, go_back_button).clicked() {
-                        if state.edit_state.is_delete() {
+                        if exec.locked && state.edit_state.is_delete() {
+                            state.command_error =
+                                Some(format!("Executor {} is locked", exec_idx + 1));
+                        } else if state.edit_state.is_delete() {
                             state.delete_confirm_executor = Some(exec_idx);
                         } else {
                             exec.go_back();
@@ -354,6 +1068,20 @@ pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState)
                         );
                     }
                 });
+                flash_osc_touched(ui, state, osc_target, column.response.rect);
+                if store_requested {
+                    let number = match state.store_cue_number.trim() {
+                        "" => Ok(None),
+                        number => number.parse().map(Some),
+                    };
+                    match number.and_then(|n| store_buffer_to_executor(state, exec_idx, n)) {
+                        Ok(_) => {
+                            state.store_cue_number.clear();
+                            state.edit_state.reset();
+                        }
+                        Err(e) => state.command_error = Some(e),
+                    }
+                }
 
                 if col < executors_per_row - 1 && exec_idx < executor_count - 1 {
                     ui.add_space(spacing);
@@ -364,12 +1092,33 @@ pub fn show_executor_panel_content(ui: &mut egui::Ui, state: &mut ConsoleState)
     }
 }
 
+fn show_submaster_faders(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    ui.heading("Submasters");
+    ui.horizontal(|ui| {
+        for (idx, level) in state.submasters.iter_mut().enumerate() {
+            ui.vertical(|ui| {
+                ui.label(RichText::new(format!("Sub {}", idx + 1)).small());
+                let response = ui.add(egui::Slider::new(level, 0.0..=1.0).vertical().text(""));
+                scroll_fader(ui, &response, level, 1.0);
+            });
+        }
+    });
+}
+
 pub fn show_dmx_console<'a>(ctx: &egui::Context, state: &mut ConsoleState) {
+    // Oops shortcut, left to text fields while they have focus
+    if ctx.memory(|m| m.focused().is_none())
+        && ctx.input(|i| i.modifiers.command && i.key_pressed(Key::Z))
+    {
+        oops(state);
+    }
+    route_keypad_to_command_line(ctx, state);
     if let Some(exec_idx) = state.editing_executor {
         show_edit_executor_panel(ctx, state, exec_idx);
     } else if let Some(exec_idx) = &state.delete_confirm_executor {
         show_confirm_prompt_panel(ctx, state, *exec_idx);
     }
+    show_executor_strip(ctx, state);
     show_sidebar_master_fader(ctx, state);
 
     egui::CentralPanel::default().show(ctx, |ui| {
@@ -387,6 +1136,8 @@ pub fn show_dmx_console<'a>(ctx: &egui::Context, state: &mut ConsoleState) {
 
         show_channels_list(ctx, state);
 
+        show_dmx_monitor(ctx, state);
+
         ui.separator();
 
         if let Some(ref error) = state.command_error {
@@ -404,20 +1155,32 @@ pub fn show_dmx_console<'a>(ctx: &egui::Context, state: &mut ConsoleState) {
         ui.heading("Console Buttons");
         ui.separator();
 
-        show_command_palette_button(state, ui);
+        if state.layout.preset == LayoutPreset::Full {
+            // Clicking the palette hands keyboard focus back to the command line
+            let palette = ui.scope(|ui| show_command_palette_button(state, ui));
+            if palette.response.contains_pointer() && ui.input(|i| i.pointer.any_click()) {
+                state.focus_command_input = true;
+            }
 
-        show_command_history(state, ui);
+            show_command_history(state, ui);
+        }
 
+        let narrow = ctx.content_rect().width() < NARROW_WINDOW_WIDTH;
         egui::SidePanel::right("executor_panel")
-            .min_width(400.0)
-            .max_width(500.0)
-            .show(ctx, |ui| match state.dmx_sub_tab {
-                DmxSubTab::Executor => {
-                    show_executor_panel_content(ui, state);
-                }
-                DmxSubTab::Fixtures => {
-                    show_fixtures_tab_content(ui, state);
-                }
+            .min_width(if narrow { 280.0 } else { 400.0 })
+            .max_width(if narrow { 360.0 } else { 500.0 })
+            .show(ctx, |ui| {
+                ScrollArea::vertical()
+                    .id_salt("executor_panel_scroll")
+                    .scroll([false, narrow])
+                    .show(ui, |ui| match state.dmx_sub_tab {
+                        DmxSubTab::Executor => {
+                            show_executor_panel_content(ui, state);
+                        }
+                        DmxSubTab::Fixtures => {
+                            show_fixtures_tab_content(ui, state);
+                        }
+                    });
             });
     });
 }
@@ -445,6 +1208,7 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 button_size,
                 egui::Button::new(RichText::new("Chan").color(Color32::GREEN)),
             )
+            .on_hover_text("Start a channel command: Chan [1-512] at [0-255]")
             .clicked()
         {
             state.command_input.push_str("Chan ");
@@ -454,11 +1218,16 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 button_size,
                 egui::Button::new(RichText::new("Fixture").color(Color32::ORANGE)),
             )
+            .on_hover_text("Start a fixture command: Fix [id] at [0-255]")
             .clicked()
         {
             state.command_input.push_str("Fix ");
         }
-        if ui.add_sized(button_size, egui::Button::new("0")).clicked() {
+        if ui
+            .add_sized(button_size, egui::Button::new("0"))
+            .on_hover_text("Type 0")
+            .clicked()
+        {
             state.command_input.push_str("0");
         }
         if ui
@@ -466,6 +1235,7 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 button_size,
                 egui::Button::new(RichText::new("B/O").color(Color32::RED)),
             )
+            .on_hover_text("Blackout: toggle the grand master between 0% and 100%")
             .clicked()
         {
             state.command_input = "b/o".to_string();
@@ -476,21 +1246,33 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
 
     ui.horizontal(|ui| {
         for num in &["1", "2", "3"] {
-            if ui.add_sized(button_size, egui::Button::new(*num)).clicked() {
+            if ui
+                .add_sized(button_size, egui::Button::new(*num))
+                .on_hover_text(format!("Type {num}"))
+                .clicked()
+            {
                 state.command_input.push_str(num);
             }
         }
     });
     ui.horizontal(|ui| {
         for num in &["4", "5", "6"] {
-            if ui.add_sized(button_size, egui::Button::new(*num)).clicked() {
+            if ui
+                .add_sized(button_size, egui::Button::new(*num))
+                .on_hover_text(format!("Type {num}"))
+                .clicked()
+            {
                 state.command_input.push_str(num);
             }
         }
     });
     ui.horizontal(|ui| {
         for num in &["7", "8", "9"] {
-            if ui.add_sized(button_size, egui::Button::new(*num)).clicked() {
+            if ui
+                .add_sized(button_size, egui::Button::new(*num))
+                .on_hover_text(format!("Type {num}"))
+                .clicked()
+            {
                 state.command_input.push_str(num);
             }
         }
@@ -501,12 +1283,13 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 button_size,
                 egui::Button::new(RichText::new("At").color(Color32::YELLOW)),
             )
+            .on_hover_text("Add \"at\", press twice (at at) to execute at full (255)")
             .clicked()
         {
             let input = state.command_input.trim_end();
             if input.ends_with("at") || input.ends_with("at ") {
                 if let Some(pos) = input.rfind("at") {
-                    let new_input = format!("{} at 255", &input[..pos]);
+                    let new_input = format!("{} at full", &input[..pos]);
                     state.command_input = new_input;
                     execute_console_command(state);
                     state.command_input.clear();
@@ -520,6 +1303,7 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 button_size,
                 egui::Button::new(RichText::new(".").color(Color32::BLUE)),
             )
+            .on_hover_text("Execute the current command at 0 (out)")
             .clicked()
         {
             if state.command_input.trim_end().ends_with("at") {
@@ -531,6 +1315,7 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
         }
         if ui
             .add_sized(button_size, egui::Button::new("please"))
+            .on_hover_text("please = execute the current command line")
             .clicked()
         {
             if !state.command_input.is_empty() {
@@ -541,15 +1326,68 @@ fn show_command_palette_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
     });
 }
 
+/// Digits and `.` typed while no widget has focus go to the command line and focus it,
+/// like the always live keypad of a console. Text fields with focus keep their keys.
+fn route_keypad_to_command_line(ctx: &egui::Context, state: &mut ConsoleState) {
+    if ctx.memory(|m| m.focused().is_some()) {
+        return;
+    }
+    let typed = ctx.input_mut(|i| {
+        let mut typed = String::new();
+        i.events.retain(|event| match event {
+            egui::Event::Text(text)
+                if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit() || c == '.') =>
+            {
+                typed.push_str(text);
+                false
+            }
+            _ => true,
+        });
+        typed
+    });
+    if !typed.is_empty() {
+        state.command_input.push_str(&typed);
+        state.focus_command_input = true;
+    }
+}
+
 fn show_console_input(state: &mut ConsoleState, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         ui.label(">");
-        let response = ui.add(TextEdit::singleline(&mut state.command_input).desired_width(300.0));
+        let id = ui.make_persistent_id("command_input");
+        // Pasted lines become separate commands instead of being joined with spaces
+        if ui.memory(|m| m.has_focus(id)) {
+            ui.input_mut(|i| {
+                for event in i.events.iter_mut() {
+                    if let egui::Event::Paste(text) = event {
+                        *text = text.lines().collect::<Vec<_>>().join("; ");
+                    }
+                }
+            });
+        }
+        let response = ui.add(
+            TextEdit::singleline(&mut state.command_input)
+                .id(id)
+                .desired_width(300.0),
+        );
+        if std::mem::take(&mut state.focus_command_input) {
+            response.request_focus();
+            // Continue after what is already typed, keypad digits are appended
+            if let Some(mut edit_state) = egui::text_edit::TextEditState::load(ui.ctx(), id) {
+                let end = egui::text::CCursor::new(state.command_input.chars().count());
+                edit_state
+                    .cursor
+                    .set_char_range(Some(egui::text::CCursorRange::one(end)));
+                edit_state.store(ui.ctx(), id);
+            }
+        }
         if response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
             if !state.command_input.is_empty() {
                 execute_console_command(state);
                 state.command_input.clear();
             }
+            // Stay on the empty line, ready for the next command
+            response.request_focus();
         }
         if ui.button("Send").clicked() {
             if !state.command_input.is_empty() {
@@ -608,31 +1446,285 @@ fn show_channels_list(ctx: &egui::Context, state: &mut ConsoleState) {
     }
 }
 
-fn show_dmx_status(state: &mut ConsoleState, ui: &mut egui::Ui) {
-    ui.horizontal(|ui| {
-        ui.heading("DMX Status:");
-        if state.dmx_connected {
-            ui.label(RichText::new("Connected").color(Color32::GREEN).strong());
-        } else if state.dmx_serial.is_some() {
-            ui.label(RichText::new("Disconnected").color(Color32::RED).strong());
-            if !state.dmx_serial_error.is_empty() {
-                ui.label(RichText::new(&state.dmx_serial_error).color(Color32::ORANGE));
-            }
-        } else {
-            ui.label(
-                RichText::new("Not Initialized")
-                    .color(Color32::YELLOW)
-                    .weak(),
-            );
-            if !state.dmx_serial_error.is_empty() {
-                ui.label(RichText::new(&state.dmx_serial_error).color(Color32::ORANGE));
+/// How long a changed channel stays outlined in the DMX monitor
+const MONITOR_HIGHLIGHT: Duration = Duration::from_millis(750);
+/// Channels per row in the DMX monitor grid
+const MONITOR_COLUMNS: usize = 32;
+
+fn show_dmx_monitor(ctx: &egui::Context, state: &mut ConsoleState) {
+    if !state.show_dmx_monitor {
+        return;
+    }
+    let now = Instant::now();
+    for (idx, &value) in state.channels.iter().enumerate() {
+        if state.monitor_last_channels.get(idx) != Some(&value) {
+            state.monitor_changed_at[idx] = Some(now);
+        }
+    }
+    state.monitor_last_channels = state.channels.clone();
+
+    // Channel index (0-based) -> owning fixture
+    let mut owners: Vec<Option<&Fixture>> = vec![None; DMX_CHANNELS];
+    for fixture in &state.fixtures {
+        for chan in state.fixture_footprint(fixture) {
+            if let Some(owner) = owners.get_mut(chan.saturating_sub(1)) {
+                *owner = Some(fixture);
             }
         }
-    });
-}
+    }
 
-fn show_sidebar_master_fader(ctx: &egui::Context, state: &mut ConsoleState) {
-    egui::SidePanel::left("master_panel")
+    let mut open = state.show_dmx_monitor;
+    egui::Window::new("DMX Monitor")
+        .open(&mut open)
+        .resizable(true)
+        .default_size([1100.0, 500.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.monitor_highlight_changes, "Highlight changes");
+                ui.checkbox(&mut state.monitor_show_owners, "Show fixtures");
+            });
+            ui.separator();
+            ScrollArea::both().show(ui, |ui| {
+                egui::Grid::new("dmx_monitor_grid")
+                    .spacing([2.0, 2.0])
+                    .show(ui, |ui| {
+                        for (idx, &value) in state.channels.iter().enumerate() {
+                            let (rect, response) =
+                                ui.allocate_exact_size(Vec2::new(30.0, 30.0), egui::Sense::hover());
+                            let painter = ui.painter();
+                            let level = 30 + (value as u16 * 225 / 255) as u8;
+                            painter.rect_filled(rect, 2.0, Color32::from_rgb(level, level, 0));
+                            let text_color = if value > 128 {
+                                Color32::BLACK
+                            } else {
+                                Color32::WHITE
+                            };
+                            painter.text(
+                                rect.left_top() + Vec2::new(2.0, 1.0),
+                                egui::Align2::LEFT_TOP,
+                                idx + 1,
+                                egui::FontId::monospace(8.0),
+                                text_color.gamma_multiply(0.6),
+                            );
+                            painter.text(
+                                rect.center_bottom() - Vec2::new(0.0, 2.0),
+                                egui::Align2::CENTER_BOTTOM,
+                                value,
+                                egui::FontId::monospace(11.0),
+                                text_color,
+                            );
+                            let owner = owners[idx];
+                            if state.monitor_show_owners
+                                && let Some(fixture) = owner
+                            {
+                                painter.text(
+                                    rect.right_top() - Vec2::new(2.0, -1.0),
+                                    egui::Align2::RIGHT_TOP,
+                                    fixture.id,
+                                    egui::FontId::monospace(8.0),
+                                    Color32::LIGHT_BLUE,
+                                );
+                            }
+                            if state.monitor_highlight_changes
+                                && state.monitor_changed_at[idx]
+                                    .is_some_and(|t| now.duration_since(t) < MONITOR_HIGHLIGHT)
+                            {
+                                painter.rect_stroke(
+                                    rect,
+                                    2.0,
+                                    (2.0, Color32::ORANGE),
+                                    egui::StrokeKind::Inside,
+                                );
+                            }
+                            let tooltip = match owner {
+                                Some(fixture) => format!(
+                                    "Ch {}: {value}\n{} (ID: {})",
+                                    idx + 1,
+                                    fixture.name,
+                                    fixture.id
+                                ),
+                                None => format!("Ch {}: {value}", idx + 1),
+                            };
+                            response.on_hover_text(tooltip);
+                            if (idx + 1) % MONITOR_COLUMNS == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+        });
+    state.show_dmx_monitor = open;
+}
+
+/// Consecutive failed agent checks before offering to reconnect the DMX dongle
+const DMX_RECONNECT_PROMPT_AFTER: u32 = 30;
+
+/// Bottom bar on every tab: patch size, live executors, connections and frame rate
+pub fn show_status_bar(ctx: &egui::Context, state: &ConsoleState) {
+    let mut patched = [false; DMX_CHANNELS];
+    for fixture in &state.fixtures {
+        let Some(mode) = state
+            .template_library
+            .get_template(fixture.template_id)
+            .and_then(|t| t.get_mode(fixture.mode_index))
+        else {
+            continue;
+        };
+        for chan_def in &mode.channels {
+            let idx = (fixture.start_channel + chan_def.offset as usize).saturating_sub(1);
+            if let Some(chan) = patched.get_mut(idx) {
+                *chan = true;
+            }
+        }
+    }
+    let patched = patched.iter().filter(|&&p| p).count();
+    let active = state
+        .executors
+        .iter()
+        .filter(|e| e.fader_level > 0.0)
+        .count();
+    let dt = ctx.input(|i| i.stable_dt);
+    let fps = if dt > 0.0 { 1.0 / dt } else { 0.0 };
+    let status = |ui: &mut egui::Ui, name: &str, on: bool| {
+        let (text, color) = if on {
+            ("on", Color32::GREEN)
+        } else {
+            ("off", Color32::GRAY)
+        };
+        ui.label(format!("{name}:"));
+        ui.label(RichText::new(text).color(color));
+    };
+
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(format!("Fixtures: {}", state.fixtures.len()));
+            ui.separator();
+            ui.label(format!("Patched: {patched}/{DMX_CHANNELS}"));
+            ui.separator();
+            ui.label(format!(
+                "Active executors: {active}/{}",
+                state.executors.len()
+            ));
+            ui.separator();
+            status(ui, "DMX", state.dmx_connected);
+            ui.separator();
+            status(ui, "OSC", state.osc_manager.1.is_some());
+            ui.separator();
+            status(ui, "HTTP", state.http_api.1.is_some());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(RichText::new(format!("{fps:.0} FPS")).weak());
+            });
+        });
+    });
+}
+
+fn show_dmx_status(state: &mut ConsoleState, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.heading("DMX Status:");
+        if state.dmx_connected {
+            ui.label(RichText::new("Connected").color(Color32::GREEN).strong());
+        } else if state.dmx_serial.is_some() {
+            ui.label(RichText::new("Disconnected").color(Color32::RED).strong());
+            if !state.dmx_serial_error.is_empty() {
+                ui.label(RichText::new(&state.dmx_serial_error).color(Color32::ORANGE));
+            }
+            if state.dmx_failed_checks >= DMX_RECONNECT_PROMPT_AFTER {
+                ui.label(
+                    RichText::new("DMX device lost, check the dongle").color(Color32::LIGHT_RED),
+                );
+                if ui
+                    .button(RichText::new("Reconnect").color(Color32::YELLOW))
+                    .clicked()
+                {
+                    reconnect_dmx(state);
+                }
+            }
+        } else {
+            ui.label(
+                RichText::new("Not Initialized")
+                    .color(Color32::YELLOW)
+                    .weak(),
+            );
+            if !state.dmx_serial_error.is_empty() {
+                ui.label(RichText::new(&state.dmx_serial_error).color(Color32::ORANGE));
+            }
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let freeze_button = if state.frozen {
+                egui::Button::new(RichText::new("FROZEN").color(Color32::BLACK).strong())
+                    .fill(Color32::from_rgb(0, 200, 255))
+            } else {
+                egui::Button::new(RichText::new("Freeze").color(Color32::WHITE).strong())
+                    .fill(Color32::from_rgb(0, 80, 120))
+            };
+            if ui
+                .add_sized(Vec2::new(120.0, 35.0), freeze_button)
+                .on_hover_text("Hold the DMX output as it is, ignoring faders, cues and the buffer")
+                .clicked()
+            {
+                state.frozen = !state.frozen;
+            }
+            egui::ComboBox::from_id_salt("freeze_mode")
+                .selected_text(state.freeze_mode.name())
+                .show_ui(ui, |ui| {
+                    for mode in [FreezeMode::Hold, FreezeMode::StopSending] {
+                        ui.selectable_value(&mut state.freeze_mode, mode, mode.name());
+                    }
+                });
+        });
+    });
+}
+
+/// Bottom row with every executor's current cue and a mini GO, visible whatever the side panel shows
+fn show_executor_strip(ctx: &egui::Context, state: &mut ConsoleState) {
+    egui::TopBottomPanel::bottom("executor_strip").show(ctx, |ui| {
+        ScrollArea::horizontal()
+            .id_salt("executor_strip_scroll")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let any_cues = state.executors.iter().any(|e| !e.cue_list.is_empty());
+                    let go_all = egui::Button::new(RichText::new("GO All").strong())
+                        .fill(Color32::DARK_GREEN);
+                    if ui
+                        .add_enabled(any_cues, go_all)
+                        .on_hover_text("GO on every executor with cues, in executor order")
+                        .clicked()
+                    {
+                        for exec in state.executors.iter_mut() {
+                            if !exec.cue_list.is_empty() {
+                                exec.go();
+                            }
+                        }
+                    }
+                    ui.separator();
+                    for (exec_idx, exec) in state.executors.iter_mut().enumerate() {
+                        ui.vertical(|ui| {
+                            ui.set_width(90.0);
+                            ui.label(RichText::new(format!("Exec {}", exec_idx + 1)).small());
+                            let cue = exec
+                                .current_cue
+                                .and(exec.cue_list.get(exec.current_cue_index));
+                            let text = match cue {
+                                Some(cue) => format!("{} {}", cue.id, cue.name),
+                                None => "-".to_string(),
+                            };
+                            ui.add(egui::Label::new(RichText::new(text).strong()).truncate());
+                            let go = egui::Button::new(RichText::new("GO").small())
+                                .fill(Color32::DARK_GREEN);
+                            if ui.add_enabled(!exec.cue_list.is_empty(), go).clicked() {
+                                exec.go();
+                            }
+                        });
+                        ui.separator();
+                    }
+                });
+            });
+    });
+}
+
+fn show_sidebar_master_fader(ctx: &egui::Context, state: &mut ConsoleState) {
+    egui::SidePanel::left("master_panel")
         .resizable(true)
         .min_width(40.0)
         .max_width(80.0)
@@ -642,43 +1734,86 @@ fn show_sidebar_master_fader(ctx: &egui::Context, state: &mut ConsoleState) {
             ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
                 ui.label(format!("{}%", (state.master_dimmer * 100.0) as u32));
                 let available_height = ui.available_height();
-                ui.add_sized(
+                let response = ui.add_sized(
                     egui::vec2(30.0, available_height),
                     egui::Slider::new(&mut state.master_dimmer, 0.0..=1.0).vertical(),
                 );
+                scroll_fader(ui, &response, &mut state.master_dimmer, 1.0);
+                flash_osc_touched(ui, state, OSCTarget::MasterDimmer, response.rect);
             });
         });
 }
 
+/// How long a control stays outlined after an OSC message acted on it
+const OSC_FLASH_DURATION: Duration = Duration::from_millis(300);
+
+/// Outlines a control recently acted on by OSC, fading out over [`OSC_FLASH_DURATION`]
+fn flash_osc_touched(ui: &egui::Ui, state: &ConsoleState, target: OSCTarget, rect: egui::Rect) {
+    let Some(touched) = state.osc_touched.get(&target) else {
+        return;
+    };
+    let elapsed = touched.elapsed();
+    if elapsed >= OSC_FLASH_DURATION {
+        return;
+    }
+    let fade = 1.0 - elapsed.as_secs_f32() / OSC_FLASH_DURATION.as_secs_f32();
+    ui.painter().rect_stroke(
+        rect.expand(2.0),
+        4.0,
+        egui::Stroke::new(2.0, Color32::from_rgb(0, 200, 255).gamma_multiply(fade)),
+        egui::StrokeKind::Outside,
+    );
+}
+
+/// Nudges a fader value with the mouse wheel while the pointer hovers it.
+/// Holding shift uses a finer step.
+fn scroll_fader(ui: &egui::Ui, response: &egui::Response, value: &mut f32, max: f32) {
+    if !response.hovered() {
+        return;
+    }
+    let (delta, fine) = ui.input(|i| {
+        // Shift-scroll is reported as horizontal scrolling by egui
+        (
+            i.raw_scroll_delta.x + i.raw_scroll_delta.y,
+            i.modifiers.shift,
+        )
+    });
+    if delta != 0.0 {
+        let step = if fine { 0.01 } else { 0.05 };
+        *value = (*value + step * delta.signum()).clamp(0.0, max);
+    }
+}
+
 fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
     let active_size = Vec2::new(120.0, 35.0);
     let normal_size = Vec2::new(80.0, 35.0);
+    let palette = state.theme.palette;
     let clear_button = egui::Button::new("Clear").fill(
         if !state.buffer.is_empty() | state.edit_state.if_any() | !state.command_input.is_empty() {
             Color32::DARK_RED
         } else {
-            Color32::GRAY
+            palette.idle
         },
     );
 
-    let store_button = egui::Button::new("Store").fill(Color32::from_rgb(0, 100, 200));
-    let store_active = egui::Button::new("Store (ACTIVE)").fill(Color32::from_rgb(0, 150, 255));
-    let store_disabled = egui::Button::new("Store").fill(Color32::GRAY);
+    let store_button = egui::Button::new("Store").fill(palette.store);
+    let store_active = egui::Button::new("Store (ACTIVE)").fill(palette.accent);
+    let store_disabled = egui::Button::new("Store").fill(palette.idle);
 
-    let edit_button = egui::Button::new("Edit").fill(Color32::from_rgb(200, 100, 0));
-    let edit_active = egui::Button::new("Edit (ACTIVE)").fill(Color32::from_rgb(255, 165, 0));
+    let edit_button = egui::Button::new("Edit").fill(palette.edit);
+    let edit_active = egui::Button::new("Edit (ACTIVE)").fill(palette.accent);
 
-    let delete_button = egui::Button::new("Delete").fill(Color32::from_rgb(150, 50, 50));
-    let delete_active = egui::Button::new("Delete (ACTIVE)").fill(Color32::from_rgb(255, 100, 100));
+    let delete_button = egui::Button::new("Delete").fill(palette.delete);
+    let delete_active = egui::Button::new("Delete (ACTIVE)").fill(palette.accent);
 
-    let label_button = egui::Button::new("Label").fill(Color32::from_rgb(50, 100, 200));
-    let label_active = egui::Button::new("Label (ACTIVE)").fill(Color32::from_rgb(100, 150, 255));
+    let label_button = egui::Button::new("Label").fill(palette.label);
+    let label_active = egui::Button::new("Label (ACTIVE)").fill(palette.accent);
 
-    let copy_button = egui::Button::new("Copy").fill(Color32::from_rgb(200, 100, 0));
-    let copy_active = egui::Button::new("Copy (ACTIVE)").fill(Color32::from_rgb(200, 100, 0));
+    let copy_button = egui::Button::new("Copy").fill(palette.copy_move);
+    let copy_active = egui::Button::new("Copy (ACTIVE)").fill(palette.accent);
 
-    let move_button = egui::Button::new("Move").fill(Color32::from_rgb(200, 100, 0));
-    let move_active = egui::Button::new("Move (ACTIVE)").fill(Color32::from_rgb(200, 100, 0));
+    let move_button = egui::Button::new("Move").fill(palette.copy_move);
+    let move_active = egui::Button::new("Move (ACTIVE)").fill(palette.accent);
 
     let buffer_button =
         egui::Button::new(RichText::new("Buffer").color(if state.buffer.is_empty() {
@@ -687,7 +1822,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
             Color32::WHITE
         }))
         .fill(if state.buffer.is_empty() {
-            Color32::GRAY
+            palette.idle
         } else {
             Color32::DARK_BLUE
         });
@@ -710,7 +1845,13 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
         } else {
             store_disabled
         };
-        if ui.add_sized(size, btn).clicked() {
+        if ui
+            .add_sized(size, btn)
+            .on_hover_text(
+                "Store the buffer: activate, then click an executor's GO to store it as a new cue",
+            )
+            .clicked()
+        {
             if !state.buffer.is_empty() {
                 state.edit_state.reset_if_set(EditingState::Store);
             }
@@ -730,6 +1871,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                     edit_button
                 },
             )
+            .on_hover_text("Edit cues: activate, then click an executor's GO to open its cue list")
             .clicked()
         {
             if state.edit_state.is_edit() | state.edit_state.is_none() {
@@ -753,6 +1895,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                     delete_button
                 },
             )
+            .on_hover_text("Delete: activate, then click an executor to delete all its cues")
             .clicked()
         {
             if state.edit_state.is_delete() | state.edit_state.is_none() {
@@ -775,6 +1918,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                     label_button
                 },
             )
+            .on_hover_text("Label: activate, then click an executor's GO to rename its cues")
             .clicked()
         {
             if state.edit_state.is_label() | state.edit_state.is_none() {
@@ -797,6 +1941,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                     copy_button
                 },
             )
+            .on_hover_text("Copy: not implemented yet, clicking a cue does nothing")
             .clicked()
         {
             if state.edit_state.is_copy() | state.edit_state.is_none() {
@@ -819,6 +1964,7 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                     move_button
                 },
             )
+            .on_hover_text("Move: activate, then click the cue to move and the cue to swap it with")
             .clicked()
         {
             if state.edit_state.is_move() | state.edit_state.is_none() {
@@ -828,15 +1974,35 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
             }
         }
 
-        if ui.add_sized(normal_size, buffer_button).clicked() {
+        if ui
+            .add_sized(normal_size, buffer_button)
+            .on_hover_text("Show the pending channel values that Store will record")
+            .clicked()
+        {
             state.show_buffer = !state.show_buffer;
         }
 
-        if ui.add_sized(normal_size, channels_button).clicked() {
+        if ui
+            .add_sized(normal_size, channels_button)
+            .on_hover_text("Show the mixed DMX output sent to the hardware")
+            .clicked()
+        {
             state.show_channels = !state.show_channels;
         }
 
-        if ui.add_sized(normal_size, clear_button).clicked() {
+        if ui
+            .add_sized(normal_size, egui::Button::new("Monitor"))
+            .on_hover_text("Show all 512 output channels as a grid")
+            .clicked()
+        {
+            state.show_dmx_monitor = !state.show_dmx_monitor;
+        }
+
+        if ui
+            .add_sized(normal_size, clear_button)
+            .on_hover_text("Clear the buffer, then the active mode, then the command line")
+            .clicked()
+        {
             if !state.buffer.is_empty() {
                 state.command_history.push(ConsoleCommand::Clear);
                 state.buffer.clear();
@@ -846,16 +2012,105 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                 state.command_input.clear();
             }
         }
+
+        let oops_button = egui::Button::new(RichText::new("Oops").color(Color32::WHITE))
+            .fill(Color32::from_rgb(150, 0, 150));
+        if ui
+            .add_sized(normal_size, oops_button)
+            .on_hover_text(format!(
+                "Restore faders, master and buffer from {}s ago (Ctrl+Z)",
+                state.oops_lookback
+            ))
+            .clicked()
+        {
+            oops(state);
+        }
+        ui.add(
+            DragValue::new(&mut state.oops_lookback)
+                .range(1..=MAX_OOPS_LOOKBACK)
+                .suffix("s"),
+        );
+
+        let strobe_button = egui::Button::new(RichText::new("Strobe").color(Color32::BLACK))
+            .fill(Color32::from_rgb(255, 255, 150));
+        if ui
+            .add_sized(normal_size, strobe_button)
+            .on_hover_text(format!(
+                "Set every shutter/strobe channel to {}",
+                state.strobe_value
+            ))
+            .clicked()
+        {
+            state.command_input = format!("all strobe {}", state.strobe_value);
+            execute_console_command(state);
+            state.command_input.clear();
+        }
+        ui.add(DragValue::new(&mut state.strobe_value).range(0..=255));
+        if ui
+            .add_sized(normal_size, egui::Button::new("Strobe Off"))
+            .on_hover_text("Set every shutter/strobe channel to 0")
+            .clicked()
+        {
+            state.command_input = "all strobe off".to_string();
+            execute_console_command(state);
+            state.command_input.clear();
+        }
+
+        if ui
+            .add_sized(normal_size, egui::Button::new("All Full"))
+            .on_hover_text("Set every fixture to full through the buffer (all at full)")
+            .clicked()
+        {
+            state.command_input = "all at full".to_string();
+            execute_console_command(state);
+            state.command_input.clear();
+        }
+        if ui
+            .add_sized(normal_size, egui::Button::new("All Off"))
+            .on_hover_text("Set every fixture to 0 through the buffer (all at 0)")
+            .clicked()
+        {
+            state.command_input = "all at 0".to_string();
+            execute_console_command(state);
+            state.command_input.clear();
+        }
+        if ui
+            .add_sized(normal_size, egui::Button::new("Grab"))
+            .on_hover_text("Copy every fixture's current intensity into the buffer (grab)")
+            .clicked()
+        {
+            state.command_input = "grab".to_string();
+            execute_console_command(state);
+            state.command_input.clear();
+        }
+
+        ui.label("Move")
+            .on_hover_text("Time pan/tilt changes take to reach their position, 0 is instant");
+        ui.add(
+            DragValue::new(&mut state.move_time)
+                .range(0.0..=MAX_MOVE_TIME)
+                .speed(0.05)
+                .suffix("s"),
+        );
     });
     match state.dmx_sub_tab {
         DmxSubTab::Executor => match state.edit_state {
             EditingState::None => {}
             EditingState::Store => {
-                ui.label(
-                    RichText::new("Click an executor to store the buffer to a new cue")
-                        .small()
-                        .color(Color32::GOLD),
-                );
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new("Click an executor to store the buffer to a new cue")
+                            .small()
+                            .color(Color32::GOLD),
+                    );
+                    ui.label("Cue");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.store_cue_number)
+                            .desired_width(50.0)
+                            .hint_text("next"),
+                    )
+                    .on_hover_text("Cue number to store, e.g. 2.5 between cues 2 and 3");
+                });
             }
             EditingState::Edit => {
                 ui.label(
@@ -882,7 +2137,13 @@ fn show_command_button(state: &mut ConsoleState, ui: &mut egui::Ui) {
                         .color(Color32::from_rgb(100, 150, 255)),
                 );
             }
-            EditingState::Copy => {}
+            EditingState::Copy => {
+                ui.label(
+                    RichText::new("Copy is not implemented yet")
+                        .small()
+                        .color(Color32::GRAY),
+                );
+            }
             EditingState::Move => {}
             EditingState::OSCLearn => {}
         },
@@ -926,8 +2187,197 @@ fn show_confirm_prompt_panel(ctx: &egui::Context, state: &mut ConsoleState, exec
         });
 }
 
+/// Layout preset picker and minimum window size, for the tab bar
+pub fn show_layout_menu(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    ui.menu_button(format!("Layout: {}", state.layout.preset.name()), |ui| {
+        for preset in [LayoutPreset::Full, LayoutPreset::Compact] {
+            if ui
+                .radio(state.layout.preset == preset, preset.name())
+                .clicked()
+            {
+                state.layout.preset = preset;
+                state.layout.min_window_size = preset.min_window_size();
+            }
+        }
+        ui.separator();
+        ui.label("Minimum window size");
+        ui.horizontal(|ui| {
+            let size = &mut state.layout.min_window_size;
+            ui.add(
+                DragValue::new(&mut size.x)
+                    .range(640.0..=3840.0)
+                    .suffix("px"),
+            );
+            ui.label("x");
+            ui.add(
+                DragValue::new(&mut size.y)
+                    .range(480.0..=2160.0)
+                    .suffix("px"),
+            );
+        });
+    });
+}
+
+/// Theme picker and accent color, for the tab bar
+pub fn show_theme_menu(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    ui.menu_button(format!("Theme: {}", state.theme.theme.name()), |ui| {
+        for theme in [Theme::Dark, Theme::Light] {
+            if ui.radio(state.theme.theme == theme, theme.name()).clicked() {
+                state.theme.theme = theme;
+            }
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Accent");
+            ui.color_edit_button_srgba(&mut state.theme.palette.accent)
+                .on_hover_text("Fill of the active Store/Edit/Delete/Label/Copy/Move button");
+        });
+        if ui.button("Reset Colors").clicked() {
+            state.theme.palette = ButtonPalette::default();
+        }
+    });
+}
+
+/// Applies the theme to the context when it changes, overriding the system theme
+pub fn apply_theme(ctx: &egui::Context, state: &mut ConsoleState) {
+    let theme = state.theme.theme;
+    if state.applied_theme != Some(theme) {
+        ctx.set_theme(theme.egui_theme());
+        state.applied_theme = Some(theme);
+    }
+}
+
+/// Sends the minimum window size to the viewport when it changes
+pub fn apply_min_window_size(ctx: &egui::Context, state: &mut ConsoleState) {
+    let size = state.layout.min_window_size;
+    if state.applied_min_window_size != Some(size) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::MinInnerSize(size));
+        state.applied_min_window_size = Some(size);
+    }
+}
+
+pub fn show_new_show_confirm(ctx: &egui::Context, state: &mut ConsoleState) {
+    if !state.new_show_confirm {
+        return;
+    }
+    egui::Window::new("Confirm New Show")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.heading("Start a new show?");
+            ui.separator();
+            ui.label("Fixtures, groups, cues, presets and audio tracks will be removed.");
+            ui.label("DMX, audio and OSC connections are kept. This action cannot be undone.");
+
+            ui.horizontal(|ui| {
+                if ui.button("Yes, New Show").clicked() {
+                    state.new_show();
+                }
+                if ui.button("Cancel").clicked() {
+                    state.new_show_confirm = false;
+                }
+            });
+        });
+}
+
+/// Small button opening the notes editor, the notes show on hover
+fn show_notes_button(ui: &mut egui::Ui, notes: &mut String) {
+    let icon = if notes.trim().is_empty() {
+        RichText::new("🗒").weak()
+    } else {
+        RichText::new("🗒").color(Color32::LIGHT_YELLOW)
+    };
+    let response = ui.menu_button(icon, |ui| {
+        ui.label("Notes");
+        ui.add(
+            TextEdit::multiline(notes)
+                .desired_rows(4)
+                .desired_width(250.0)
+                .hint_text("Intent, reminders..."),
+        );
+    });
+    if notes.trim().is_empty() {
+        response.response.on_hover_text("Add notes");
+    } else {
+        response.response.on_hover_text(notes.as_str());
+    }
+}
+
+/// Draws a cue thumbnail as a row of small swatches
+fn show_cue_thumbnail(ui: &mut egui::Ui, swatches: &[Color32]) {
+    let cell = Vec2::new(6.0, 20.0);
+    let (rect, response) = ui.allocate_exact_size(
+        Vec2::new(cell.x * THUMBNAIL_CELLS as f32, cell.y),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, Color32::from_gray(20));
+    for (i, color) in swatches.iter().enumerate() {
+        let min = rect.min + Vec2::new(cell.x * i as f32, 0.0);
+        painter.rect_filled(egui::Rect::from_min_size(min, cell), 0.0, *color);
+    }
+    response.on_hover_text(format!("{} fixture(s) lit", swatches.len()));
+}
+
+/// One line per auto-follow chain of the executor: its cues and total runtime
+fn show_follow_chains(ui: &mut egui::Ui, executor: &Executor, chains: &[FollowChain]) {
+    for chain in chains {
+        let first = &executor.cue_list[chain.cues[0]];
+        let last = &executor.cue_list[chain.cues[chain.cues.len() - 1]];
+        let text = if chain.loops {
+            format!(
+                "⟳ Cue {} to Cue {} loops every {:.1}s",
+                first.id, last.id, chain.runtime
+            )
+        } else {
+            format!(
+                "➡ Cue {} to Cue {} runs {:.1}s",
+                first.id, last.id, chain.runtime
+            )
+        };
+        ui.label(RichText::new(text).color(Color32::LIGHT_BLUE))
+            .on_hover_text("Delays, fades and follow times of the auto-follow chain");
+    }
+}
+
+/// Time of the cue in its auto-follow chain, and an arrow when it follows into the next cue
+fn show_follow_marker(ui: &mut egui::Ui, timing: Option<(f32, bool)>) {
+    let (text, hint) = match timing {
+        Some((start, true)) => (format!("+{start:.1}s ↓"), "Follows into the next cue"),
+        Some((start, false)) => (format!("+{start:.1}s ■"), "Ends the auto-follow chain"),
+        None => (String::new(), ""),
+    };
+    let response = ui.add_sized(
+        Vec2::new(60.0, 20.0),
+        egui::Label::new(RichText::new(text).small().color(Color32::LIGHT_BLUE)),
+    );
+    if timing.is_some() {
+        response.on_hover_text(hint);
+    }
+}
+
 fn show_edit_executor_panel(ctx: &egui::Context, state: &mut ConsoleState, exec_idx: usize) {
     let mut exec_command = false;
+    let thumbnails = state.cue_thumbnails(exec_idx);
+    let chains = state
+        .executors
+        .get(exec_idx)
+        .map(Executor::follow_chains)
+        .unwrap_or_default();
+    // Cue index -> (seconds into its chain, follows into the next cue)
+    let chain_starts: std::collections::HashMap<usize, (f32, bool)> = chains
+        .iter()
+        .flat_map(|chain| {
+            let last = chain.cues.len() - 1;
+            chain
+                .cues
+                .iter()
+                .zip(&chain.starts)
+                .enumerate()
+                .map(move |(pos, (&idx, &start))| (idx, (start, pos < last || chain.loops)))
+        })
+        .collect();
     egui::Window::new("Cue List")
         .collapsible(true)
         .resizable(true)
@@ -935,6 +2385,9 @@ fn show_edit_executor_panel(ctx: &egui::Context, state: &mut ConsoleState, exec_
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
             ui.heading(format!("Executor {} - Cue List", exec_idx + 1));
+            if let Some(executor) = state.executors.get(exec_idx) {
+                show_follow_chains(ui, executor, &chains);
+            }
             ui.separator();
 
             if let Some(executor) = state.executors.get_mut(exec_idx) {
@@ -943,94 +2396,135 @@ fn show_edit_executor_panel(ctx: &egui::Context, state: &mut ConsoleState, exec_
                 } else {
                     ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
                         ui.vertical(|ui| {
-                            executor.cue_list.iter_mut().for_each(|cue| {
-                                // CUE ITERATION
-                                ui.horizontal(|ui| {
-                                    if ui
-                                        .add_sized(
-                                            Vec2::new(120.0, 35.0),
-                                            egui::Button::new(
-                                                RichText::new(format!(
-                                                    "[Executor {}] {} ID: {}",
-                                                    executor.id.saturating_add(1), // Base 1 instead of base 0
-                                                    cue.name,
-                                                    cue.id,
-                                                ))
-                                                .color(Color32::GRAY),
-                                            ),
-                                        )
-                                        .clicked()
-                                    {
-                                        match state.edit_state {
-                                            EditingState::Move => {
-                                                if state
-                                                    .command_input
-                                                    .to_lowercase()
-                                                    .trim_end()
-                                                    .ends_with("to exec")
-                                                {
-                                                    state.command_input = format!(
-                                                        "{} {} Cue {}",
-                                                        state.command_input.trim_end(),
-                                                        exec_idx.saturating_add(1),
-                                                        cue.id
-                                                    );
-                                                    state
-                                                        .edit_state
-                                                        .reset_if_set(EditingState::Move);
-                                                    exec_command = true;
-                                                } else {
-                                                    state.command_input = format!(
-                                                        "Move Exec {} Cue {} To Exec ",
-                                                        exec_idx.saturating_add(1),
-                                                        cue.id
-                                                    );
-                                                }
+                            executor
+                                .cue_list
+                                .iter_mut()
+                                .enumerate()
+                                .for_each(|(i, cue)| {
+                                    // CUE ITERATION
+                                    ui.horizontal(|ui| {
+                                        let key = (exec_idx, cue.id);
+                                        let mut selected = state.selected_cues.contains(&key);
+                                        if ui.checkbox(&mut selected, "").changed() {
+                                            if selected {
+                                                state.selected_cues.insert(key);
+                                            } else {
+                                                state.selected_cues.remove(&key);
                                             }
-                                            _ => {}
                                         }
-                                    }
-                                    if let Some(cue_idx) = state.labeling_cue
-                                        && state.edit_state.is_label()
-                                        && cue_idx == cue.id
-                                    {
-                                        ui.add_sized(
-                                            Vec2::new(120.0, 35.0),
-                                            egui::TextEdit::singleline(&mut state.label_buffer),
-                                        );
+                                        show_follow_marker(ui, chain_starts.get(&i).copied());
+                                        show_cue_thumbnail(ui, &thumbnails[i]);
                                         if ui
                                             .add_sized(
                                                 Vec2::new(120.0, 35.0),
-                                                egui::Button::new("Save Cue Name"),
+                                                egui::Button::new(
+                                                    RichText::new(format!(
+                                                        "[Executor {}] {} ID: {}",
+                                                        executor.id.saturating_add(1), // Base 1 instead of base 0
+                                                        cue.name,
+                                                        cue.id,
+                                                    ))
+                                                    .color(Color32::GRAY),
+                                                ),
                                             )
                                             .clicked()
                                         {
-                                            cue.name = state.label_buffer.clone();
-                                            state.label_buffer.clear();
-                                            state.edit_state.reset();
-                                            state.labeling_cue = None;
+                                            match state.edit_state {
+                                                EditingState::Move => {
+                                                    if state
+                                                        .command_input
+                                                        .to_lowercase()
+                                                        .trim_end()
+                                                        .ends_with("to exec")
+                                                    {
+                                                        state.command_input = format!(
+                                                            "{} {} Cue {}",
+                                                            state.command_input.trim_end(),
+                                                            exec_idx.saturating_add(1),
+                                                            cue.id
+                                                        );
+                                                        state
+                                                            .edit_state
+                                                            .reset_if_set(EditingState::Move);
+                                                        exec_command = true;
+                                                    } else {
+                                                        state.command_input = format!(
+                                                            "Move Exec {} Cue {} To Exec ",
+                                                            exec_idx.saturating_add(1),
+                                                            cue.id
+                                                        );
+                                                    }
+                                                }
+                                                _ => {}
+                                            }
                                         }
-                                    }
-                                    if state.edit_state.is_label()
-                                        && state.labeling_cue != Some(cue.id)
-                                    {
-                                        if ui
-                                            .add_sized(
+                                        if let Some(cue_idx) = state.labeling_cue
+                                            && state.edit_state.is_label()
+                                            && cue_idx == cue.id
+                                        {
+                                            ui.add_sized(
                                                 Vec2::new(120.0, 35.0),
-                                                egui::Button::new("Rename"),
-                                            )
-                                            .clicked()
+                                                egui::TextEdit::singleline(&mut state.label_buffer),
+                                            );
+                                            if ui
+                                                .add_sized(
+                                                    Vec2::new(120.0, 35.0),
+                                                    egui::Button::new("Save Cue Name"),
+                                                )
+                                                .clicked()
+                                            {
+                                                cue.name = state.label_buffer.clone();
+                                                state.label_buffer.clear();
+                                                state.edit_state.reset();
+                                                state.labeling_cue = None;
+                                            }
+                                        }
+                                        if state.edit_state.is_label()
+                                            && state.labeling_cue != Some(cue.id)
                                         {
-                                            state.labeling_cue = Some(cue.id);
+                                            if ui
+                                                .add_sized(
+                                                    Vec2::new(120.0, 35.0),
+                                                    egui::Button::new("Rename"),
+                                                )
+                                                .clicked()
+                                            {
+                                                state.labeling_cue = Some(cue.id);
+                                            }
                                         }
-                                    }
-                                    ui.add_sized(
-                                        Vec2::new(120.0, 35.0),
-                                        egui::DragValue::new(&mut cue.fade_time)
-                                            .range(0.0..=f32::MAX),
-                                    );
+                                        ui.add_sized(
+                                            Vec2::new(120.0, 35.0),
+                                            egui::DragValue::new(&mut cue.fade_time)
+                                                .range(0.0..=f32::MAX),
+                                        );
+                                        egui::ComboBox::from_id_salt(("fade_curve", cue.id))
+                                            .selected_text(cue.fade_curve.name())
+                                            .show_ui(ui, |ui| {
+                                                for curve in FadeCurve::ALL {
+                                                    ui.selectable_value(
+                                                        &mut cue.fade_curve,
+                                                        curve,
+                                                        curve.name(),
+                                                    );
+                                                }
+                                            });
+                                        ui.checkbox(&mut cue.disabled, "Disabled")
+                                            .on_hover_text("Skip this cue on GO and GO BACK");
+                                        let mut has_follow = cue.follow.is_some();
+                                        if ui.checkbox(&mut has_follow, "Follow").changed() {
+                                            cue.follow = if has_follow { Some(0.0) } else { None };
+                                        }
+                                        if let Some(follow) = &mut cue.follow {
+                                            ui.add(
+                                                egui::DragValue::new(follow)
+                                                    .range(0.0..=f32::MAX)
+                                                    .suffix("s")
+                                                    .speed(0.1),
+                                            );
+                                        }
+                                        show_notes_button(ui, &mut cue.notes);
+                                    });
                                 });
-                            });
                         });
                     });
                 }
@@ -1132,9 +2626,11 @@ fn show_edit_executor_panel(ctx: &egui::Context, state: &mut ConsoleState, exec_
                     }
                 });
             } */
+            show_batch_move(ui, state, exec_idx);
             if ui.button("Close").clicked() {
                 state.editing_executor = None;
                 state.edit_state.set(EditingState::None);
+                state.selected_cues.clear();
             }
         });
     if exec_command {
@@ -1143,6 +2639,36 @@ fn show_edit_executor_panel(ctx: &egui::Context, state: &mut ConsoleState, exec_
     }
 }
 
+/// "Move selected to Exec N" for the cues ticked in the cue list window
+fn show_batch_move(ui: &mut egui::Ui, state: &mut ConsoleState, exec_idx: usize) {
+    // Only this executor's cues, which may have been deleted or renumbered since
+    if let Some(executor) = state.executors.get(exec_idx) {
+        state
+            .selected_cues
+            .retain(|&(exec, cue)| exec == exec_idx && executor.cue_index(cue).is_some());
+    }
+    ui.horizontal(|ui| {
+        let count = state.selected_cues.len();
+        let clicked = ui
+            .add_enabled(
+                count > 0,
+                egui::Button::new(format!("Move {count} selected to Exec")),
+            )
+            .clicked();
+        ui.add(
+            DragValue::new(&mut state.batch_move_target).range(1..=state.executors.len() as u32),
+        );
+        if clicked {
+            let cues: Vec<CueNumber> = state.selected_cues.iter().map(|&(_, cue)| cue).collect();
+            let exec_from = exec_idx as u32 + 1;
+            match move_cues(state, exec_from, &cues, state.batch_move_target) {
+                Ok(()) => state.selected_cues.clear(),
+                Err(e) => state.command_error = Some(e),
+            }
+        }
+    });
+}
+
 pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
     ui.heading("Fixtures");
     ui.separator();
@@ -1152,9 +2678,13 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
         ui.selectable_value(&mut state.fixtures_tab, FixturesTab::Grouping, "Grouping");
         ui.selectable_value(&mut state.fixtures_tab, FixturesTab::Editing, "Editing");
         ui.selectable_value(&mut state.fixtures_tab, FixturesTab::List, "List");
+        ui.selectable_value(&mut state.fixtures_tab, FixturesTab::Presets, "Presets");
     });
     ui.separator();
 
+    show_patch_warnings(ui, state);
+    show_group_intensity_fader(ui, state);
+
     match state.fixtures_tab {
         FixturesTab::Creation => {
             ui.heading("Create Fixture");
@@ -1245,6 +2775,28 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                 }
             }
 
+            let preview_channels = state
+                .selected_template_id
+                .and_then(|id| state.template_library.get_template(id))
+                .and_then(|t| t.get_mode(state.selected_mode_index))
+                .map(|m| m.channels.len())
+                .unwrap_or(0);
+            if preview_channels > 0 {
+                // An empty field patches at channel 1
+                let start_ch = match state.new_fixture_start_channel.as_str() {
+                    "" => Some(1),
+                    text => text.parse::<usize>().ok().filter(|&ch| ch > 0),
+                };
+                match start_ch {
+                    Some(start_ch) => {
+                        show_patch_preview(ui, state, start_ch..start_ch + preview_channels)
+                    }
+                    None => {
+                        ui.label(RichText::new("Invalid start channel").color(Color32::RED));
+                    }
+                }
+            }
+
             if ui.button("Add Fixture").clicked() {
                 let start_ch = state
                     .new_fixture_start_channel
@@ -1260,16 +2812,9 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
 
                     let end_ch = start_ch + num_channels - 1;
 
-                    let collision = state.fixtures.iter().any(|f| {
-                        let f_template = state.template_library.get_template(f.template_id);
-                        let f_num_channels = f_template
-                            .and_then(|t| t.get_mode(f.mode_index))
-                            .map(|m| m.channels.len())
-                            .unwrap_or(0);
-                        let f_end_ch = f.start_channel + f_num_channels - 1;
-
-                        start_ch <= f_end_ch && end_ch >= f.start_channel
-                    });
+                    let collision = !state
+                        .fixtures_overlapping(&(start_ch..end_ch + 1))
+                        .is_empty();
 
                     if collision {
                         state.fixture_error = Some(format!(
@@ -1309,7 +2854,8 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                 .max_height(200.0)
                 .show(ui, |ui| {
                     let mut to_remove: Option<usize> = None;
-                    for fixture in &state.fixtures {
+                    let mut renamed: Option<(u32, String)> = None;
+                    for fixture in &mut state.fixtures {
                         let template_name = state
                             .template_library
                             .get_template(fixture.template_id)
@@ -1324,19 +2870,59 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                             .unwrap_or_else(|| "Unknown".to_string());
 
                         ui.horizontal(|ui| {
+                            let rename_id = egui::Id::new(("rename_fixture", fixture.id));
+                            if state.renaming_fixture == Some(fixture.id) {
+                                let response = ui.add(
+                                    TextEdit::singleline(&mut state.label_buffer)
+                                        .id(rename_id)
+                                        .desired_width(150.0),
+                                );
+                                // Enter or clicking away saves, Escape or an empty name cancels
+                                if response.lost_focus() {
+                                    let name = state.label_buffer.trim();
+                                    if ui.input(|i| i.key_pressed(egui::Key::Escape))
+                                        || name.is_empty()
+                                    {
+                                        state.renaming_fixture = None;
+                                        state.label_buffer.clear();
+                                    } else {
+                                        renamed = Some((fixture.id, name.into()));
+                                    }
+                                }
+                                if state.label_buffer.trim().is_empty() {
+                                    ui.label(
+                                        RichText::new("Name can't be empty").color(Color32::RED),
+                                    );
+                                }
+                            } else if ui
+                                .selectable_label(false, &fixture.name)
+                                .on_hover_text("Click to rename")
+                                .clicked()
+                            {
+                                state.renaming_fixture = Some(fixture.id);
+                                state.label_buffer = fixture.name.clone();
+                                ui.memory_mut(|m| m.request_focus(rename_id));
+                            }
                             ui.label(format!(
-                                "{} (ID: {}) - {} (Mode: {}) - Ch {}",
-                                fixture.name,
-                                fixture.id,
-                                template_name,
-                                mode,
-                                fixture.start_channel
+                                "(ID: {}) - {} (Mode: {}) - Ch {}",
+                                fixture.id, template_name, mode, fixture.start_channel
                             ));
+                            show_notes_button(ui, &mut fixture.notes);
+                            ui.checkbox(&mut fixture.enabled, "").on_hover_text(
+                                "Enabled. Disabled fixtures output 0 on all their channels",
+                            );
                             if ui.button("✕").clicked() {
                                 to_remove = Some(fixture.id as usize);
                             }
                         });
                     }
+                    if let Some((id, name)) = renamed
+                        && let Some(fixture) = state.fixtures.iter_mut().find(|f| f.id == id)
+                    {
+                        fixture.name = name;
+                        state.renaming_fixture = None;
+                        state.label_buffer.clear();
+                    }
                     if let Some(id) = to_remove {
                         state.fixtures.retain(|f| f.id as u32 != id as u32);
                     }
@@ -1350,43 +2936,32 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                 ui.label("Group Name:");
                 ui.add(TextEdit::singleline(&mut state.new_group_name).desired_width(150.0));
                 ui.label("Grid #:");
-                let mut grid_input = state.new_group_grid_index.unwrap_or(1).to_string();
-                ui.add(TextEdit::singleline(&mut grid_input).desired_width(40.0));
-                if let Ok(idx) = grid_input.parse::<usize>() {
-                    state.new_group_grid_index = Some(idx);
-                }
+                ui.add(TextEdit::singleline(&mut state.new_group_grid_input).desired_width(40.0));
             });
+            let grid_index =
+                parse_group_grid_index(&state.new_group_grid_input, &state.fixture_groups);
+            if let Err(error) = &grid_index {
+                ui.label(RichText::new(error).color(Color32::RED));
+            }
 
-            if ui.button("Create Group").clicked() {
-                if !state.new_group_name.is_empty() {
-                    let new_id = state.fixture_groups.len() as u32 + 1;
-                    let mut group = FixtureGroup::new(new_id, state.new_group_name.clone());
-                    group.grid_index = state.new_group_grid_index;
-                    state.fixture_groups.push(group);
-                    state.new_group_name.clear();
-                    state.new_group_grid_index = None;
-                }
+            if ui
+                .add_enabled(grid_index.is_ok(), egui::Button::new("Create Group"))
+                .clicked()
+                && let Ok(grid_index) = grid_index
+                && !state.new_group_name.is_empty()
+            {
+                let new_id = state.fixture_groups.len() as u32 + 1;
+                let mut group = FixtureGroup::new(new_id, state.new_group_name.clone());
+                group.grid_index = grid_index;
+                state.fixture_groups.push(group);
+                state.new_group_name.clear();
+                state.new_group_grid_input.clear();
             }
 
             ui.separator();
 
             ui.heading("Add Fixtures to Group");
-            ui.label("Select fixtures:");
-            ScrollArea::vertical()
-                .id_salt("fixtures_select")
-                .max_height(100.0)
-                .show(ui, |ui| {
-                    for fixture in &state.fixtures {
-                        let is_selected = state.selected_fixture_ids.contains(&fixture.id);
-                        if ui.selectable_label(is_selected, &fixture.name).clicked() {
-                            if is_selected {
-                                state.selected_fixture_ids.retain(|&id| id != fixture.id);
-                            } else {
-                                state.selected_fixture_ids.push(fixture.id);
-                            }
-                        }
-                    }
-                });
+            show_fixture_selection(ui, state);
 
             ui.separator();
             ui.heading("Groups");
@@ -1426,15 +3001,13 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                 "Click a cell to select that group, then use console to control (e.g., 'at at')",
             );
 
-            let grid_cols = 10;
-            let grid_rows = 10;
             let cell_size = 40.0;
 
             egui::Grid::new("group_grid")
-                .num_columns(grid_cols)
+                .num_columns(GROUP_GRID_COLS)
                 .spacing([5.0, 5.0])
                 .show(ui, |ui| {
-                    for idx in 1..=grid_cols * grid_rows {
+                    for idx in 1..=GROUP_GRID_COLS * GROUP_GRID_ROWS {
                         let group = state
                             .fixture_groups
                             .iter()
@@ -1480,7 +3053,7 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                             }
                         }
 
-                        if idx % grid_cols == 0 {
+                        if idx % GROUP_GRID_COLS == 0 {
                             ui.end_row();
                         }
                     }
@@ -1492,6 +3065,8 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
 
             if let Some(fix_id) = state.selected_fixture_id {
                 let mut exec_command = false;
+                let mut new_mode: Option<usize> = None;
+                let mut shutter_template: Option<u32> = None;
                 if let Some(fixture) = state.fixtures.iter_mut().find(|f| f.id == fix_id) {
                     ui.label(format!("Fixture: {} (ID: {})", fixture.name, fixture.id));
                     ui.label(format!("Start Channel: {}", fixture.start_channel));
@@ -1499,9 +3074,36 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                     let template = state.template_library.get_template(fixture.template_id);
                     if let Some(tmpl) = template {
                         if let Some(mode) = tmpl.get_mode(fixture.mode_index) {
-                            ui.label(format!("Mode: {}", mode.name));
+                            let mut mode_index = fixture.mode_index;
+                            egui::ComboBox::from_id_salt("edit_mode_select")
+                                .selected_text(format!("Mode: {}", mode.name))
+                                .show_ui(ui, |ui| {
+                                    for (idx, mode) in tmpl.modes.iter().enumerate() {
+                                        let label =
+                                            format!("{} ({}ch)", mode.name, mode.channels.len());
+                                        ui.selectable_value(&mut mode_index, idx, label);
+                                    }
+                                });
+                            if mode_index != fixture.mode_index {
+                                new_mode = Some(mode_index);
+                            }
                         }
                     }
+                    ui.horizontal(|ui| {
+                        ui.label("Intensity limits");
+                        let max = fixture.intensity_max;
+                        ui.add(
+                            DragValue::new(&mut fixture.intensity_min)
+                                .range(0..=max)
+                                .prefix("Min "),
+                        );
+                        let min = fixture.intensity_min;
+                        ui.add(
+                            DragValue::new(&mut fixture.intensity_max)
+                                .range(min..=u8::MAX)
+                                .prefix("Max "),
+                        );
+                    });
                     ui.label("Dimmer");
                     let slider = egui::Slider::new(&mut fixture.intensity, 0..=u8::MAX);
                     if ui.add_sized(Vec2::new(120.0, 35.0), slider).changed() {
@@ -1512,23 +3114,51 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                     ui.separator();
                     ui.heading("Color");
 
+                    let color_channels: Vec<ChannelType> = template
+                        .and_then(|t| t.get_mode(fixture.mode_index))
+                        .map(|m| m.channels.iter().map(|c| c.channel_type).collect())
+                        .unwrap_or_default();
                     ui.label("White");
                     let slider = egui::Slider::new(&mut fixture.color.w, 0..=u8::MAX);
                     if ui.add_sized(Vec2::new(120.0, 35.0), slider).changed() {
-                        let r = fixture.color.r;
-                        let g = fixture.color.g;
-                        let b = fixture.color.b;
+                        // Resend the current look without its white, then the new white on top
+                        let without_white = Color {
+                            w: 0,
+                            ..fixture.color.clone()
+                        };
+                        let (r, g, b) = without_white.rendered_rgb(&color_channels);
                         let w = fixture.color.w;
                         state.command_input = format!("Fix {fix_id} Color R{r} G{g} B{b} W{w}");
                         exec_command = true;
                     }
-                    let mut color32 =
-                        Color32::from_rgb(fixture.color.r, fixture.color.g, fixture.color.b);
+                    let has_cct = template
+                        .and_then(|t| t.get_mode(fixture.mode_index))
+                        .is_some_and(|m| {
+                            m.channels.iter().any(|c| {
+                                matches!(c.channel_type, ChannelType::CTO | ChannelType::CTB)
+                            })
+                        });
+                    if has_cct {
+                        ui.label("Color Temperature");
+                        let slider = egui::Slider::new(&mut fixture.cct, CCT_WARMEST..=CCT_COOLEST)
+                            .suffix("K");
+                        if ui.add_sized(Vec2::new(120.0, 35.0), slider).changed() {
+                            state.command_input = format!("Fix {fix_id} CCT {}", fixture.cct);
+                            exec_command = true;
+                        }
+                    }
+                    let (r, g, b) = fixture.color.rendered_rgb(&color_channels);
+                    let mut color32 = Color32::from_rgb(r, g, b);
                     if ui.color_edit_button_srgba(&mut color32).changed() {
                         let r = color32.r();
                         let g = color32.g();
                         let b = color32.b();
-                        let w = fixture.color.w;
+                        // The picked color already includes the white
+                        let w = if color_channels.contains(&ChannelType::White) {
+                            0
+                        } else {
+                            fixture.color.w
+                        };
 
                         ui.label(format!("R: {r} G: {g} B: {b} W: {w}"));
 
@@ -1537,9 +3167,88 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                         state.command_input = format!("Fix {fix_id} {color_cmd}");
                         exec_command = true;
                     }
+
+                    let beam_channels: Vec<_> = template
+                        .and_then(|t| t.get_mode(fixture.mode_index))
+                        .map(|m| {
+                            m.channels
+                                .iter()
+                                .filter(|c| {
+                                    matches!(
+                                        c.channel_type,
+                                        ChannelType::Prism | ChannelType::Frost
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let has_strobe = template
+                        .and_then(|t| t.get_mode(fixture.mode_index))
+                        .is_some_and(|m| {
+                            m.has_channel(ChannelType::Shutter)
+                                || m.has_channel(ChannelType::Strobe)
+                        });
+                    if has_strobe && let Some(tmpl) = template {
+                        ui.separator();
+                        ui.heading("Strobe");
+                        let map = tmpl.shutter_map();
+                        let mut hz = map.strobe_hz(fixture.shutter);
+                        let slider = egui::Slider::new(&mut hz, 0.0..=map.max_hz)
+                            .suffix(" Hz")
+                            .max_decimals(1);
+                        if ui
+                            .add_sized(Vec2::new(120.0, 35.0), slider)
+                            .on_hover_text("0 Hz opens the shutter")
+                            .changed()
+                        {
+                            state.command_input = format!("Fix {fix_id} Strobe {hz:.1}");
+                            exec_command = true;
+                        }
+                        if ui
+                            .add_enabled(map.can_close(), egui::Button::new("Close Shutter"))
+                            .on_disabled_hover_text(
+                                "Set a closed value in the template's shutter map first",
+                            )
+                            .clicked()
+                        {
+                            state.command_input = format!("Fix {fix_id} Shutter Closed");
+                            exec_command = true;
+                        }
+                        shutter_template = Some(tmpl.id);
+                    }
+
+                    if !beam_channels.is_empty() {
+                        ui.separator();
+                        ui.heading("Beam");
+                    }
+                    for chan_def in beam_channels {
+                        let name = chan_def.channel_type.name();
+                        let mut value = fixture
+                            .custom_values
+                            .get(&(chan_def.offset as usize))
+                            .copied()
+                            .unwrap_or_default();
+                        ui.label(name);
+                        let slider = egui::Slider::new(&mut value, 0..=u8::MAX);
+                        if ui.add_sized(Vec2::new(120.0, 35.0), slider).changed() {
+                            state.command_input = format!("Fix {fix_id} {name} {value}");
+                            exec_command = true;
+                        }
+                    }
                 } else {
                     ui.label("Fixture not found. Select a fixture from the List tab.");
                 }
+                if let Some(template) =
+                    shutter_template.and_then(|id| state.template_library.get_template_mut(id))
+                {
+                    show_shutter_map_editor(ui, template);
+                }
+                if let Some(mode_index) = new_mode {
+                    state.fixture_warning = state.set_fixture_mode(fix_id, mode_index);
+                }
+                if let Some(warning) = &state.fixture_warning {
+                    ui.label(RichText::new(warning).color(Color32::ORANGE));
+                }
                 if exec_command {
                     execute_console_command(state);
                     state.command_input.clear();
@@ -1581,32 +3290,332 @@ pub fn show_fixtures_tab_content(ui: &mut egui::Ui, state: &mut ConsoleState) {
                             ))
                         };
 
-                        if ui.add_sized(Vec2::new(200.0, 30.0), btn).clicked() {
-                            if !is_selected {
-                                state.selected_fixture_id = Some(fixture.id);
-                                state.command_input = format!("Fix {}", fixture.id);
-                            } else {
-                                state.selected_fixture_id = None;
-                                state.command_input = state
-                                    .command_input
-                                    .replace(&format!("Fix {}", fixture.id), "");
-                            }
-                        }
+                        if ui.add_sized(Vec2::new(200.0, 30.0), btn).clicked() {
+                            state.fixture_warning = None;
+                            if !is_selected {
+                                state.selected_fixture_id = Some(fixture.id);
+                                state.command_input = format!("Fix {}", fixture.id);
+                            } else {
+                                state.selected_fixture_id = None;
+                                state.command_input = state
+                                    .command_input
+                                    .replace(&format!("Fix {}", fixture.id), "");
+                            }
+                        }
+
+                        if fixture.enabled {
+                            ui.label(RichText::new(template_name).small().weak());
+                        } else {
+                            ui.label(
+                                RichText::new(format!("{template_name} - disabled"))
+                                    .small()
+                                    .color(Color32::ORANGE),
+                            );
+                        }
+                    }
+                });
+
+            ui.separator();
+            if let Some(fix_id) = state.selected_fixture_id {
+                ui.label(
+                    RichText::new(format!("Selected: Fixture {}", fix_id)).color(Color32::GREEN),
+                );
+            }
+        }
+        FixturesTab::Presets => {
+            ui.heading("Presets");
+            ui.separator();
+
+            show_fixture_selection(ui, state);
+            ui.label(
+                RichText::new(format!(
+                    "{} fixture(s) selected",
+                    state.selected_fixture_ids.len()
+                ))
+                .small()
+                .weak(),
+            );
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("preset_kind")
+                    .selected_text(state.new_preset_kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in [PresetKind::Color, PresetKind::Position, PresetKind::Beam] {
+                            ui.selectable_value(&mut state.new_preset_kind, kind, kind.name());
+                        }
+                    });
+                ui.label("Name:");
+                ui.add(TextEdit::singleline(&mut state.new_preset_name).desired_width(120.0));
+                if ui.button("Store Preset").clicked() && !state.new_preset_name.is_empty() {
+                    let name = state.new_preset_name.clone();
+                    match state.store_preset(state.new_preset_kind, name) {
+                        Ok(()) => {
+                            state.new_preset_name.clear();
+                            state.fixture_error = None;
+                        }
+                        Err(e) => state.fixture_error = Some(e),
+                    }
+                }
+            });
+            if let Some(error) = &state.fixture_error {
+                ui.label(RichText::new(error).color(Color32::RED));
+            }
+
+            ui.separator();
+            let mut to_apply: Option<u32> = None;
+            let mut to_remove: Option<u32> = None;
+            ScrollArea::vertical()
+                .id_salt("presets_list")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for kind in [PresetKind::Color, PresetKind::Position, PresetKind::Beam] {
+                        let presets: Vec<&Preset> =
+                            state.presets.iter().filter(|p| p.kind == kind).collect();
+                        if presets.is_empty() {
+                            continue;
+                        }
+                        ui.label(RichText::new(kind.name()).strong());
+                        ui.horizontal_wrapped(|ui| {
+                            for preset in presets {
+                                if ui
+                                    .button(&preset.name)
+                                    .on_hover_text("Apply to the selected fixtures")
+                                    .clicked()
+                                {
+                                    to_apply = Some(preset.id);
+                                }
+                                if ui.small_button("✕").clicked() {
+                                    to_remove = Some(preset.id);
+                                }
+                            }
+                        });
+                    }
+                });
+            if let Some(id) = to_apply {
+                state.apply_preset(id);
+            }
+            if let Some(id) = to_remove {
+                state.presets.retain(|p| p.id != id);
+            }
+        }
+    }
+}
+
+/// Bar of the whole universe showing the patched fixtures and where a new fixture
+/// would land, red where it collides or goes past the last channel
+fn show_patch_preview(ui: &mut egui::Ui, state: &ConsoleState, footprint: std::ops::Range<usize>) {
+    let (rect, _) =
+        ui.allocate_exact_size(Vec2::new(ui.available_width(), 14.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, Color32::from_gray(30));
+    let span = |range: &std::ops::Range<usize>| {
+        let x = |chan: usize| {
+            let t = (chan.saturating_sub(1) as f32 / DMX_CHANNELS as f32).min(1.0);
+            rect.left() + t * rect.width()
+        };
+        egui::Rect::from_x_y_ranges(
+            x(range.start)..=x(range.end).max(x(range.start) + 1.0),
+            rect.y_range(),
+        )
+    };
+    for fixture in &state.fixtures {
+        painter.rect_filled(
+            span(&state.fixture_footprint(fixture)),
+            0.0,
+            Color32::from_gray(90),
+        );
+    }
+
+    let colliding = state.fixtures_overlapping(&footprint);
+    let past_end = footprint.end > DMX_CHANNELS + 1;
+    let color = if colliding.is_empty() && !past_end {
+        Color32::GREEN
+    } else {
+        Color32::RED
+    };
+    painter.rect_filled(span(&footprint), 0.0, color.gamma_multiply(0.8));
+
+    let range = format!("Channels {} to {}", footprint.start, footprint.end - 1);
+    if past_end {
+        ui.label(
+            RichText::new(format!("{range}, beyond channel {DMX_CHANNELS}")).color(Color32::RED),
+        );
+    } else if colliding.is_empty() {
+        ui.label(RichText::new(range).color(Color32::GREEN));
+    } else {
+        let names: Vec<&str> = colliding.iter().map(|f| f.name.as_str()).collect();
+        ui.label(
+            RichText::new(format!("{range} collide with: {}", names.join(", ")))
+                .color(Color32::RED),
+        );
+    }
+}
+
+/// Lists fixtures sharing DMX channels, hidden while the patch is clean
+fn show_patch_warnings(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    let conflicts = state.cached_patch_conflicts().to_vec();
+    if conflicts.is_empty() {
+        return;
+    }
+    let name = |id: u32| {
+        state
+            .fixtures
+            .iter()
+            .find(|f| f.id == id)
+            .map(|f| f.name.as_str())
+            .unwrap_or("?")
+    };
+    egui::CollapsingHeader::new(
+        RichText::new(format!("⚠ Patch warnings ({})", conflicts.len())).color(Color32::ORANGE),
+    )
+    .id_salt("patch_warnings")
+    .default_open(true)
+    .show(ui, |ui| {
+        for (a, b, range) in &conflicts {
+            ui.label(
+                RichText::new(format!(
+                    "{} (ID: {a}) and {} (ID: {b}) share channels {} to {}",
+                    name(*a),
+                    name(*b),
+                    range.start,
+                    range.end - 1
+                ))
+                .color(Color32::ORANGE),
+            );
+        }
+    });
+    ui.separator();
+}
+
+/// Live intensity fader for the group selected in the grid
+fn show_group_intensity_fader(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    let Some(group) = state
+        .selected_group_id
+        .and_then(|id| state.fixture_groups.iter().find(|g| g.id == id))
+    else {
+        return;
+    };
+    let (group_id, group_name) = (group.id, group.name.clone());
+    let mut level = match state.group_intensity {
+        Some((id, level)) if id == group_id => level,
+        // Start from the brightest member when a new group is picked
+        _ => state
+            .fixtures
+            .iter()
+            .filter(|f| group.fixture_ids.contains(&f.id))
+            .map(|f| f.intensity)
+            .max()
+            .unwrap_or_default(),
+    };
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(format!("Group {group_name}")).strong());
+        let slider = egui::Slider::new(&mut level, 0..=u8::MAX).text("Intensity");
+        if ui.add(slider).changed() {
+            state.set_group_intensity(group_id, level);
+        }
+    });
+    state.group_intensity = Some((group_id, level));
+    ui.separator();
+}
 
-                        ui.label(RichText::new(template_name).small().weak());
-                    }
-                });
+/// Size of the group grid in the Grouping tab
+const GROUP_GRID_COLS: usize = 10;
+const GROUP_GRID_ROWS: usize = 10;
 
-            ui.separator();
-            if let Some(fix_id) = state.selected_fixture_id {
-                ui.label(
-                    RichText::new(format!("Selected: Fixture {}", fix_id)).color(Color32::GREEN),
-                );
+/// Validates a typed group grid position: empty for none, otherwise a free cell of the grid
+fn parse_group_grid_index(input: &str, groups: &[FixtureGroup]) -> Result<Option<usize>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let max = GROUP_GRID_COLS * GROUP_GRID_ROWS;
+    match input.parse::<usize>() {
+        Ok(idx) if (1..=max).contains(&idx) => {
+            match groups.iter().find(|g| g.grid_index == Some(idx)) {
+                Some(group) => Err(format!("Grid {idx} is already used by {}", group.name)),
+                None => Ok(Some(idx)),
             }
         }
+        _ => Err(format!("Grid # must be between 1 and {max}")),
     }
 }
 
+/// DMX ranges of a template's shutter channel, shared by every fixture using the template
+fn show_shutter_map_editor(ui: &mut egui::Ui, template: &mut FixtureTemplate) {
+    egui::CollapsingHeader::new(format!("Shutter mapping ({})", template.name))
+        .id_salt("shutter_map")
+        .show(ui, |ui| {
+            let mut map = template.shutter_map();
+            egui::Grid::new("shutter_map_grid").show(ui, |ui| {
+                ui.label("Open");
+                ui.add(DragValue::new(&mut map.open));
+                ui.label("Closed");
+                ui.add(DragValue::new(&mut map.closed));
+                ui.end_row();
+                ui.label("Strobe from");
+                ui.add(DragValue::new(&mut map.strobe_start));
+                ui.label("to");
+                ui.add(DragValue::new(&mut map.strobe_end));
+                ui.end_row();
+                ui.label("Slowest");
+                ui.add(
+                    DragValue::new(&mut map.min_hz)
+                        .range(0.1..=map.max_hz)
+                        .speed(0.1)
+                        .suffix(" Hz"),
+                );
+                ui.label("Fastest");
+                ui.add(
+                    DragValue::new(&mut map.max_hz)
+                        .range(map.min_hz..=100.0)
+                        .speed(0.1)
+                        .suffix(" Hz"),
+                );
+                ui.end_row();
+            });
+            if map != template.shutter_map() {
+                template.shutter_map = Some(map);
+            }
+            if template.shutter_map.is_some() && ui.button("Reset to linear 1-255").clicked() {
+                template.shutter_map = None;
+            }
+        });
+}
+
+/// Toggle list of all fixtures, editing `selected_fixture_ids`, with quick filters by channel type
+fn show_fixture_selection(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Select all with:");
+        for filter in FixtureFilter::ALL {
+            if ui
+                .small_button(filter.name())
+                .on_hover_text("Shift+click to add to the selection")
+                .clicked()
+            {
+                let add = ui.input(|i| i.modifiers.shift);
+                state.select_fixtures_matching(filter, add);
+            }
+        }
+    });
+    ui.label("Select fixtures:");
+    ScrollArea::vertical()
+        .id_salt("fixtures_select")
+        .max_height(100.0)
+        .show(ui, |ui| {
+            for fixture in &state.fixtures {
+                let is_selected = state.selected_fixture_ids.contains(&fixture.id);
+                if ui.selectable_label(is_selected, &fixture.name).clicked() {
+                    if is_selected {
+                        state.selected_fixture_ids.retain(|&id| id != fixture.id);
+                    } else {
+                        state.selected_fixture_ids.push(fixture.id);
+                    }
+                }
+            }
+        });
+}
+
 pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
     egui::CentralPanel::default().show(ctx, |ui| {
         ui.horizontal(|ui| {
@@ -1640,10 +3649,99 @@ pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                 .clicked()
             {
                 use crate::osc::OSCManager;
-                state.osc_manager.1 = OSCManager::from(state.osc_manager.0.clone()).ok();
-                state.osc_manager.0.clear();
+                // Release the old socket before binding, the new address may reuse its port
+                drop(state.osc_manager.1.take());
+                match OSCManager::from(state.osc_manager.0.clone()) {
+                    Ok(manager) => {
+                        state.osc_manager.1 = Some(manager);
+                        state.osc_manager.0.clear();
+                        state.osc_error = None;
+                    }
+                    Err(e) => state.osc_error = Some(e.to_string()),
+                }
+            }
+        });
+        ui.separator();
+        ui.heading(RichText::new("OSC Feedback").color(Color32::ORANGE));
+        ui.horizontal(|ui| {
+            ui.add_sized(
+                Vec2::new(150.0, 35.0),
+                egui::TextEdit::singleline(&mut state.osc_feedback.0),
+            );
+            if ui
+                .add_sized(
+                    Vec2::new(120.0, 35.0),
+                    egui::Button::new(RichText::new("Connect").color(Color32::DARK_GREEN)),
+                )
+                .on_hover_text("Send master volume and audio transport state to this IP:Port")
+                .clicked()
+            {
+                use crate::osc::OSCFeedback;
+                match OSCFeedback::from(state.osc_feedback.0.clone()) {
+                    Ok(feedback) => {
+                        state.osc_feedback.1 = Some(feedback);
+                        state.osc_feedback.0.clear();
+                        state.osc_error = None;
+                    }
+                    Err(e) => state.osc_error = Some(e.to_string()),
+                }
+            }
+            if let Some(feedback) = &state.osc_feedback.1 {
+                ui.label(
+                    RichText::new(format!("Sending to {}", feedback.target()))
+                        .color(Color32::GREEN),
+                );
+            }
+        });
+        if let Some(error) = &state.osc_error {
+            ui.label(RichText::new(error).color(Color32::RED));
+        }
+        ui.separator();
+        ui.heading(RichText::new("HTTP API").color(Color32::ORANGE));
+        ui.horizontal(|ui| {
+            ui.add_sized(
+                Vec2::new(150.0, 35.0),
+                egui::TextEdit::singleline(&mut state.http_api.0).hint_text("127.0.0.1:8080"),
+            );
+            if let Some(api) = &state.http_api.1 {
+                if ui
+                    .add_sized(
+                        Vec2::new(120.0, 35.0),
+                        egui::Button::new(RichText::new("Stop").color(Color32::RED)),
+                    )
+                    .clicked()
+                {
+                    state.http_api.1 = None;
+                } else {
+                    ui.label(
+                        RichText::new(format!("Serving on http://{}", api.address()))
+                            .color(Color32::GREEN),
+                    );
+                }
+            } else if ui
+                .add_sized(
+                    Vec2::new(120.0, 35.0),
+                    egui::Button::new(RichText::new("Start").color(Color32::DARK_GREEN)),
+                )
+                .on_hover_text(
+                    "GET /status for executors, masters and audio as JSON, \
+                     POST /command with {\"command\": \"go exec 1\"} as JSON to run a command line",
+                )
+                .clicked()
+            {
+                match crate::http_api::HttpApi::start(&state.http_api.0) {
+                    Ok(api) => {
+                        state.http_api.1 = Some(api);
+                        state.http_api.0.clear();
+                        state.http_api_error = None;
+                    }
+                    Err(e) => state.http_api_error = Some(e.to_string()),
+                }
             }
         });
+        if let Some(error) = &state.http_api_error {
+            ui.label(RichText::new(error).color(Color32::RED));
+        }
 
         if state.osc_manager.1.is_some() {
             ui.separator();
@@ -1674,6 +3772,10 @@ pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                                 ui.text_edit_singleline(
                                     &mut state.osc_address_manager.master_volume,
                                 );
+                                show_osc_range(
+                                    ui,
+                                    &mut state.osc_address_manager.master_volume_range,
+                                );
                             });
                             ui.horizontal(|ui| {
                                 ui.label(RichText::new("Go"));
@@ -1694,6 +3796,7 @@ pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                             ui.horizontal(|ui| {
                                 ui.label(RichText::new("Master Dimmer"));
                                 ui.text_edit_singleline(&mut state.osc_address_manager.master_dmx);
+                                show_osc_range(ui, &mut state.osc_address_manager.master_dmx_range);
                             });
                             ui.heading("Executors");
                             ui.separator();
@@ -1708,6 +3811,10 @@ pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                                 ui.text_edit_singleline(
                                     &mut state.osc_address_manager.executor_dimmer,
                                 );
+                                show_osc_range(
+                                    ui,
+                                    &mut state.osc_address_manager.executor_dimmer_range,
+                                );
                             });
                             ui.horizontal(|ui| {
                                 ui.label(RichText::new("Executors Go"));
@@ -1726,27 +3833,16 @@ pub fn show_midi_osc_tab(ctx: &egui::Context, state: &mut ConsoleState) {
     });
 }
 
-pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
-    // Update audio engine (for fade handling)
-    if let Some(ref mut engine) = state.audio_engine {
-        engine.set_master_volume(state.master_volume);
-        engine.update();
-
-        // Handle follow/continue for ended tracks
-        let ended_tracks = engine.get_ended_tracks();
-        for (track_id, action) in ended_tracks {
-            if action == AudioAction::Follow {
-                if let Some(idx) = state.audio_tracks.iter().position(|t| t.id == track_id) {
-                    let next_idx = idx.saturating_add(1) % state.audio_tracks.len();
-                    if let Some(next_track) = state.audio_tracks.get(next_idx) {
-                        let _ = engine.play(next_track, state.master_volume);
-                        state.audio_index = next_idx;
-                    }
-                }
-            }
-        }
-    }
+/// Min/max of the values a controller sends on a fader binding
+fn show_osc_range(ui: &mut egui::Ui, range: &mut crate::osc::OSCRange) {
+    ui.label("Min");
+    ui.add(DragValue::new(&mut range.min).speed(0.01));
+    ui.label("Max");
+    ui.add(DragValue::new(&mut range.max).speed(0.01))
+        .on_hover_text("Value the controller sends at the top of the fader, e.g. 1, 100 or 127");
+}
 
+pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
     egui::SidePanel::left("audio_master_panel")
         .min_width(60.0)
         .max_width(80.0)
@@ -1756,16 +3852,17 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
             ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
                 ui.label(format!("{}%", (state.master_volume * 100.0) as u32));
                 let available_height = ui.available_height();
-                ui.add_sized(
+                let response = ui.add_sized(
                     egui::vec2(30.0, available_height),
-                    egui::Slider::new(&mut state.master_volume, 0.0..=1.5).vertical(),
+                    egui::Slider::new(&mut state.master_volume, 0.0..=MAX_MASTER_VOLUME).vertical(),
                 );
+                flash_osc_touched(ui, state, OSCTarget::MasterVolume, response.rect);
             });
             if ui.input(|i| i.key_pressed(Key::ArrowUp)) {
-                state.master_volume = (state.master_volume + 0.01).clamp(0.0, 1.5);
+                state.master_volume = (state.master_volume + 0.01).clamp(0.0, MAX_MASTER_VOLUME);
             }
             if ui.input(|i| i.key_pressed(Key::ArrowDown)) {
-                state.master_volume = (state.master_volume - 0.01).clamp(0.0, 1.5);
+                state.master_volume = (state.master_volume - 0.01).clamp(0.0, MAX_MASTER_VOLUME);
             }
             if ui.input(|i| i.key_pressed(Key::F)) {
                 state.master_volume = 1.0;
@@ -1869,22 +3966,47 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
         ui.separator();
 
         ui.horizontal(|ui| {
-            if ui.button("Add Track").clicked() | ui.input(|i| i.key_pressed(Key::A)) {
-                if let Some(path) = rfd::FileDialog::new().pick_file() {
-                    let file_name = path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "Untitled".to_string());
-                    let file_path = path.to_string_lossy().to_string();
-                    let new_id = state.audio_tracks.len() as u32 + 1;
-
-                    // Get duration using lofty
-                    let duration = crate::audio::AudioEngine::get_duration(&file_path);
-
-                    let mut track = AudioTrack::new(new_id, file_name, file_path);
-                    track.duration = duration;
-                    state.audio_tracks.push(track);
-                }
+            if ui
+                .add_enabled(state.file_dialog.is_none(), egui::Button::new("Add Track"))
+                .clicked()
+                | ui.input(|i| i.key_pressed(Key::A))
+            {
+                open_file_dialog(state, FileDialogPurpose::AddAudioTrack);
+            }
+
+            if ui
+                .add_enabled(
+                    state.file_dialog.is_none(),
+                    egui::Button::new("Load Playlist"),
+                )
+                .on_hover_text("Replace the tracks with a saved playlist")
+                .clicked()
+            {
+                open_file_dialog(state, FileDialogPurpose::LoadPlaylist);
+            }
+
+            if ui
+                .add_enabled(
+                    state.file_dialog.is_none() && !state.audio_tracks.is_empty(),
+                    egui::Button::new("Save Playlist"),
+                )
+                .clicked()
+            {
+                open_file_dialog(state, FileDialogPurpose::SavePlaylist);
+            }
+
+            if ui
+                .button("Recheck Files")
+                .on_hover_text("Flag tracks whose audio file was moved or deleted")
+                .clicked()
+            {
+                let missing: Vec<String> = state
+                    .audio_tracks
+                    .iter_mut()
+                    .filter_map(|track| (!track.check_file()).then(|| track.name.clone()))
+                    .collect();
+                state.audio_error =
+                    (!missing.is_empty()).then(|| format!("Missing files: {}", missing.join(", ")));
             }
 
             if ui.button("Stop All").clicked() | ui.input(|i| i.key_pressed(Key::Escape)) {
@@ -1894,6 +4016,10 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
             }
         });
 
+        if let Some(message) = &state.audio_error {
+            ui.label(RichText::new(message).color(Color32::RED));
+        }
+
         ui.separator();
 
         ScrollArea::vertical()
@@ -1911,7 +4037,13 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                         .map(|e| e.is_playing(track.id))
                         .unwrap_or(false);
 
-                    egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
+                    let mut frame = egui::Frame::group(&egui::Style::default());
+                    if track.missing {
+                        frame = frame
+                            .fill(Color32::from_rgb(60, 0, 0))
+                            .stroke(egui::Stroke::new(1.0, Color32::RED));
+                    }
+                    frame.show(ui, |ui| {
                         ui.horizontal(|ui| {
                             // Up button
                             if ui.button("⬆️").clicked() {
@@ -1930,13 +4062,27 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                                 }
                             } else {
                                 if ui.button("▶").clicked() {
-                                    if let Some(ref engine) = state.audio_engine {
-                                        let _ = engine.play(track, state.master_volume);
+                                    if let Some(ref engine) = state.audio_engine
+                                        && let Err(e) = engine.play(track, state.master_volume)
+                                    {
+                                        track.check_file();
+                                        state.audio_error = Some(format!("{}: {}", track.name, e));
                                     }
                                 }
                             }
 
-                            if ui.selectable_label(is_selected, &track.name).clicked() {
+                            let name = if track.missing {
+                                RichText::new(&track.name).color(Color32::RED)
+                            } else {
+                                RichText::new(&track.name)
+                            };
+                            let name_label = ui.selectable_label(is_selected, name);
+                            if track.missing {
+                                name_label
+                                    .clone()
+                                    .on_hover_text(format!("File not found: {}", track.file_path));
+                            }
+                            if name_label.clicked() {
                                 state.selected_audio_track_id = Some(track.id);
                             }
 
@@ -1994,69 +4140,47 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
                                         );
                                     });
                             });
+                            show_fade_timeline(ui, track);
                             ui.horizontal(|ui| {
                                 ui.label("Fade In:");
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut track.fade_in,
-                                        0.0..=track.duration
-                                            - (track.fade_out
-                                                + (track.duration
-                                                    - track.end_point.unwrap_or(track.duration))
-                                                + track.start_point),
-                                    )
-                                    .text("s"),
-                                );
+                                let range = track.fade_in_range();
+                                ui.add(egui::Slider::new(&mut track.fade_in, range).text("s"));
                             });
                             ui.horizontal(|ui| {
                                 ui.label("Fade Out:");
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut track.fade_out,
-                                        0.0..=track.duration
-                                            - (track.fade_in
-                                                + track.start_point
-                                                + (track.duration
-                                                    - track.end_point.unwrap_or(track.duration))),
-                                    )
-                                    .text("s"),
-                                );
+                                let range = track.fade_out_range();
+                                ui.add(egui::Slider::new(&mut track.fade_out, range).text("s"));
                             });
                             ui.horizontal(|ui| {
                                 ui.label("Start:");
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut track.start_point,
-                                        0.0..=track.duration
-                                            - track.fade_in
-                                            - track.fade_out
-                                            - (track.duration
-                                                - track.end_point.unwrap_or(track.duration)),
-                                    )
-                                    .text("s"),
-                                );
+                                let range = track.start_range();
+                                ui.add(egui::Slider::new(&mut track.start_point, range).text("s"));
                             });
                             ui.horizontal(|ui| {
                                 ui.label("End:");
-                                let mut end_val = track.end_point.unwrap_or(track.duration);
-                                ui.add(
-                                    egui::Slider::new(
-                                        &mut end_val,
-                                        track.fade_in + track.fade_out + track.start_point
-                                            ..=track.duration,
-                                    )
-                                    .text("s"),
-                                );
+                                let mut end_val = track.end();
+                                let range = track.end_range();
+                                ui.add(egui::Slider::new(&mut end_val, range).text("s"));
                                 track.end_point = if end_val > 0.0 && end_val < track.duration {
                                     Some(end_val)
                                 } else {
                                     None
                                 };
                             });
+                            track.clamp_edit_points();
                             ui.horizontal(|ui| {
                                 ui.label("Volume:");
                                 ui.add(egui::Slider::new(&mut track.volume, 0.0..=1.0));
                             });
+                            ui.horizontal(|ui| {
+                                ui.label("Trim:");
+                                ui.add(
+                                    DragValue::new(&mut track.trim_db)
+                                        .range(-12.0..=6.0)
+                                        .speed(0.1)
+                                        .suffix(" dB"),
+                                );
+                            });
                         }
                     });
                     ui.separator();
@@ -2084,23 +4208,214 @@ pub fn show_audio_tab(ctx: &egui::Context, state: &mut ConsoleState) {
     });
 }
 
-pub fn audio_go(state: &mut ConsoleState, track_count: usize) {
-    if track_count > 0 {
-        let idx = state.audio_index;
-        if let Some(ref engine) = state.audio_engine {
-            if let Some(track) = state.audio_tracks.get(idx) {
-                let _ = engine.play(track, state.master_volume);
-
-                // Handle continue: play next track at the same time
-                if track.action == AudioAction::Continue {
-                    if let Some(next_track) = state.audio_tracks.get(idx + 1) {
-                        let _ = engine.play(next_track, state.master_volume);
-                    }
+fn open_show(state: &mut ConsoleState, path: std::path::PathBuf) {
+    match ShowFile::load_from_path(&path) {
+        Ok(show) => {
+            show.apply_to(state);
+            state.show_path = Some(path);
+        }
+        Err(e) => state.show_file_error = Some(e.to_string()),
+    }
+}
+
+fn save_show(state: &mut ConsoleState, path: std::path::PathBuf) {
+    match ShowFile::from_state(state).save_to_path(&path) {
+        Ok(()) => {
+            state.show_path = Some(path);
+            state.show_file_error = None;
+        }
+        Err(e) => state.show_file_error = Some(e.to_string()),
+    }
+}
+
+/// Open/Save buttons and the current show file, for the tab bar
+pub fn show_show_file_buttons(ui: &mut egui::Ui, state: &mut ConsoleState) {
+    if ui
+        .button("Save Show As")
+        .on_hover_text("Save the show to a new file")
+        .clicked()
+    {
+        open_file_dialog(state, FileDialogPurpose::SaveShow);
+    }
+    if ui.button("Save Show").clicked() {
+        match state.show_path.clone() {
+            Some(path) => save_show(state, path),
+            None => open_file_dialog(state, FileDialogPurpose::SaveShow),
+        }
+    }
+    if ui.button("Open Show").clicked() {
+        open_file_dialog(state, FileDialogPurpose::OpenShow);
+    }
+    if let Some(error) = &state.show_file_error {
+        ui.label(RichText::new(error).color(Color32::RED));
+    } else if let Some(name) = state.show_path.as_ref().and_then(|p| p.file_name()) {
+        ui.label(RichText::new(name.to_string_lossy()).weak());
+    }
+}
+
+fn load_playlist(state: &mut ConsoleState, path: std::path::PathBuf) {
+    match crate::audio::load_playlist(&path) {
+        Ok((tracks, missing)) => {
+            // Track ids now point to other tracks
+            if let Some(ref engine) = state.audio_engine {
+                engine.stop_all();
+            }
+            state.audio_tracks = tracks;
+            state.audio_index = 0;
+            state.audio_error = if missing.is_empty() {
+                None
+            } else {
+                Some(format!("Missing files: {}", missing.join(", ")))
+            };
+        }
+        Err(e) => state.audio_error = Some(e),
+    }
+}
+
+fn save_playlist(state: &mut ConsoleState, path: std::path::PathBuf) {
+    state.audio_error = crate::audio::save_playlist(&state.audio_tracks, &path).err();
+}
+
+fn add_audio_track(state: &mut ConsoleState, path: std::path::PathBuf) {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string());
+    let file_path = path.to_string_lossy().to_string();
+    let new_id = state.audio_tracks.len() as u32 + 1;
+
+    // Get duration using lofty
+    let duration = crate::audio::AudioEngine::get_duration(&file_path);
+
+    let mut track = AudioTrack::new(new_id, file_name, file_path);
+    track.duration = duration;
+    track.check_file();
+    state.audio_tracks.push(track);
+}
+
+/// Opens a file dialog on the tokio runtime so the UI keeps running.
+/// The picked path is handled by [`poll_file_dialog`]; only one dialog is open at a time.
+pub fn open_file_dialog(state: &mut ConsoleState, purpose: FileDialogPurpose) {
+    if state.file_dialog.is_some() {
+        return;
+    }
+    let (sender, receiver) = crossbeam_channel::bounded(1);
+    tokio::spawn(async move {
+        let dialog = rfd::AsyncFileDialog::new();
+        let file = match purpose {
+            FileDialogPurpose::AddAudioTrack => dialog.pick_file().await,
+            FileDialogPurpose::OpenShow => dialog.add_filter("Show", &["json"]).pick_file().await,
+            FileDialogPurpose::SaveShow => {
+                dialog
+                    .add_filter("Show", &["json"])
+                    .set_file_name("show.json")
+                    .save_file()
+                    .await
+            }
+            FileDialogPurpose::LoadPlaylist => {
+                dialog.add_filter("Playlist", &["json"]).pick_file().await
+            }
+            FileDialogPurpose::SavePlaylist => {
+                dialog
+                    .add_filter("Playlist", &["json"])
+                    .set_file_name("playlist.json")
+                    .save_file()
+                    .await
+            }
+        };
+        let _ = sender.send(file.map(|f| f.path().to_path_buf()));
+    });
+    state.file_dialog = Some((purpose, receiver));
+}
+
+/// Applies the result of the pending file dialog once the user closed it
+pub fn poll_file_dialog(state: &mut ConsoleState) {
+    let Some((purpose, receiver)) = &state.file_dialog else {
+        return;
+    };
+    let purpose = *purpose;
+    let path = match receiver.try_recv() {
+        Ok(path) => path,
+        Err(crossbeam_channel::TryRecvError::Empty) => return,
+        Err(crossbeam_channel::TryRecvError::Disconnected) => None,
+    };
+    state.file_dialog = None;
+    if let Some(path) = path {
+        match purpose {
+            FileDialogPurpose::AddAudioTrack => add_audio_track(state, path),
+            FileDialogPurpose::OpenShow => open_show(state, path),
+            FileDialogPurpose::SaveShow => save_show(state, path),
+            FileDialogPurpose::LoadPlaylist => load_playlist(state, path),
+            FileDialogPurpose::SavePlaylist => save_playlist(state, path),
+        }
+    }
+}
+
+/// Runs the audio engine: fades, and follow for the tracks that just ended
+pub fn update_audio(state: &mut ConsoleState) {
+    if let Some(ref mut engine) = state.audio_engine {
+        engine.set_master_volume(state.master_volume);
+        engine.update();
+
+        // Handle follow/continue for ended tracks
+        let ended_tracks = engine.get_ended_tracks();
+        for (track_id, action) in ended_tracks {
+            if action == AudioAction::Follow {
+                if let Some(idx) = state.audio_tracks.iter().position(|t| t.id == track_id) {
+                    let next_idx = idx.saturating_add(1) % state.audio_tracks.len();
+                    play_audio_chain(state, next_idx);
                 }
             }
         }
-        // Auto increment index after GO
-        increment_audio_index(state, track_count);
+    }
+}
+
+/// Plays a track, flagging it and reporting why when it can't be played
+fn play_audio_track(state: &mut ConsoleState, idx: usize) {
+    let (Some(engine), Some(track)) = (&state.audio_engine, state.audio_tracks.get_mut(idx)) else {
+        return;
+    };
+    if let Err(e) = engine.play(track, state.master_volume) {
+        track.check_file();
+        state.audio_error = Some(format!("{}: {}", track.name, e));
+    }
+}
+
+/// Tracks started together from `idx`: the track, then the next one as long as
+/// the previous track is set to Continue, stopping at the end of the list
+fn continue_chain(tracks: &[AudioTrack], idx: usize) -> std::ops::Range<usize> {
+    if idx >= tracks.len() {
+        return idx..idx;
+    }
+    let mut end = idx + 1;
+    while end < tracks.len() && tracks[end - 1].action == AudioAction::Continue {
+        end += 1;
+    }
+    idx..end
+}
+
+/// What a GO on `idx` does: the tracks it starts, and the audio index after it,
+/// past the last track started so the next GO doesn't replay it
+fn audio_go_targets(tracks: &[AudioTrack], idx: usize) -> (std::ops::Range<usize>, usize) {
+    let chain = continue_chain(tracks, idx);
+    let next = if chain.is_empty() { idx + 1 } else { chain.end };
+    (chain, next % tracks.len().max(1))
+}
+
+/// Plays the track at `idx` with the tracks it continues into, then moves the audio index
+fn play_audio_chain(state: &mut ConsoleState, idx: usize) {
+    let (chain, next) = audio_go_targets(&state.audio_tracks, idx);
+    for track_idx in chain {
+        play_audio_track(state, track_idx);
+    }
+    state.audio_index = next;
+}
+
+/// Plays the track at the audio index with its Continue chain and moves the
+/// index to the next track that hasn't been started
+pub fn audio_go(state: &mut ConsoleState, track_count: usize) {
+    if track_count > 0 {
+        play_audio_chain(state, state.audio_index);
     }
 }
 
@@ -2115,6 +4430,92 @@ fn decrement_audio_index(state: &mut ConsoleState, track_count: usize) {
     }
 }
 
+/// Timeline of the track between its start and end points, with the fade in and
+/// fade out drawn as ramps. Dragging a ramp's top corner sets its fade time.
+fn show_fade_timeline(ui: &mut egui::Ui, track: &mut AudioTrack) {
+    if track.duration <= 0.0 {
+        return;
+    }
+    let (rect, _) =
+        ui.allocate_exact_size(Vec2::new(ui.available_width(), 36.0), egui::Sense::hover());
+    let end = track.end();
+    let x = |time: f32| rect.left() + (time / track.duration).clamp(0.0, 1.0) * rect.width();
+    let pos_to_time = |pos_x: f32| (pos_x - rect.left()) / rect.width() * track.duration;
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, Color32::from_gray(30));
+    let played = egui::Rect::from_x_y_ranges(x(track.start_point)..=x(end), rect.y_range());
+    painter.rect_filled(played, 0.0, Color32::from_gray(60));
+
+    let fade_color = Color32::from_rgb(80, 140, 220).gamma_multiply(0.6);
+    let fade_in_end = x(track.start_point + track.fade_in);
+    let fade_out_start = x(end - track.fade_out);
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            played.left_bottom(),
+            egui::pos2(fade_in_end, rect.top()),
+            egui::pos2(fade_in_end, rect.bottom()),
+        ],
+        fade_color,
+        egui::Stroke::NONE,
+    ));
+    painter.add(egui::Shape::convex_polygon(
+        vec![
+            egui::pos2(fade_out_start, rect.top()),
+            played.right_bottom(),
+            egui::pos2(fade_out_start, rect.bottom()),
+        ],
+        fade_color,
+        egui::Stroke::NONE,
+    ));
+
+    let max_fade_in = *track.fade_in_range().end();
+    let max_fade_out = *track.fade_out_range().end();
+    let handle_size = Vec2::splat(10.0);
+    let fade_in_handle =
+        egui::Rect::from_center_size(egui::pos2(fade_in_end, rect.top() + 5.0), handle_size);
+    let fade_out_handle =
+        egui::Rect::from_center_size(egui::pos2(fade_out_start, rect.top() + 5.0), handle_size);
+    let fade_in_response = ui
+        .interact(
+            fade_in_handle,
+            ui.id().with(("fade_in", track.id)),
+            egui::Sense::drag(),
+        )
+        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal)
+        .on_hover_text(format!("Fade in {:.1}s", track.fade_in));
+    if fade_in_response.dragged()
+        && let Some(pointer) = fade_in_response.interact_pointer_pos()
+    {
+        track.fade_in = (pos_to_time(pointer.x) - track.start_point).clamp(0.0, max_fade_in);
+    }
+    let fade_out_response = ui
+        .interact(
+            fade_out_handle,
+            ui.id().with(("fade_out", track.id)),
+            egui::Sense::drag(),
+        )
+        .on_hover_cursor(egui::CursorIcon::ResizeHorizontal)
+        .on_hover_text(format!("Fade out {:.1}s", track.fade_out));
+    if fade_out_response.dragged()
+        && let Some(pointer) = fade_out_response.interact_pointer_pos()
+    {
+        track.fade_out = (end - pos_to_time(pointer.x)).clamp(0.0, max_fade_out);
+    }
+
+    for (handle, response) in [
+        (fade_in_handle, &fade_in_response),
+        (fade_out_handle, &fade_out_response),
+    ] {
+        let color = if response.hovered() || response.dragged() {
+            Color32::WHITE
+        } else {
+            Color32::LIGHT_BLUE
+        };
+        painter.circle_filled(handle.center(), 4.0, color);
+    }
+}
+
 fn format_duration(seconds: f32) -> String {
     let mins = (seconds as u32) / 60;
     let secs = (seconds as u32) % 60;
@@ -2130,3 +4531,117 @@ pub fn show_liveshow_tab(ctx: &egui::Context, state: &mut ConsoleState) {
         ui.separator();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dmx_types::Cue;
+
+    #[test]
+    fn smaller_mode_clears_the_freed_channels_in_cues() {
+        let mut state = ConsoleState::without_hardware();
+        let template_id = |name: &str| {
+            let templates = &state.template_library.templates;
+            templates.iter().find(|t| t.name == name).unwrap().id
+        };
+        let (moving_head, dimmer) = (
+            template_id("Generic Moving Head"),
+            template_id("Generic Dimmer"),
+        );
+        state
+            .fixtures
+            .push(Fixture::new(1, "Spot".into(), 1, moving_head, 0));
+        let wide = state.fixture_footprint(&state.fixtures[0]);
+        // Patched over the end of the spot's widest mode
+        let shared = wide.end - 1;
+        state
+            .fixtures
+            .push(Fixture::new(2, "Dimmer".into(), shared, dimmer, 0));
+        let mut cue = Cue::new(CueNumber::FIRST);
+        cue.levels = vec![255; DMX_CHANNELS];
+        state.executors[0].insert_cue(cue).unwrap();
+
+        assert_eq!(state.set_fixture_mode(1, 2), None);
+        let narrow = state.fixture_footprint(&state.fixtures[0]);
+        assert!(narrow.end < shared);
+        let levels = &state.executors[0].cue_list[0].levels;
+        for chan in wide {
+            let expected = if narrow.contains(&chan) || chan == shared {
+                255
+            } else {
+                0
+            };
+            assert_eq!(levels[chan - 1], expected, "channel {chan}");
+        }
+    }
+
+    #[test]
+    fn thumbnails_of_removed_cues_are_dropped() {
+        let mut state = ConsoleState::without_hardware();
+        for number in ["1", "2", "2.5"] {
+            let cue = Cue::new(number.parse().unwrap());
+            state.executors[0].insert_cue(cue).unwrap();
+        }
+        state.executors[1]
+            .insert_cue(Cue::new(CueNumber::FIRST))
+            .unwrap();
+        state.cue_thumbnails(0);
+        state.cue_thumbnails(1);
+        assert_eq!(state.cue_thumbnails.len(), 4);
+
+        state.executors[0].remove_cue(1);
+        assert_eq!(state.cue_thumbnails(0).len(), 2);
+        assert_eq!(state.cue_thumbnails.len(), 3);
+        assert!(
+            !state
+                .cue_thumbnails
+                .contains_key(&(0, CueNumber::whole(2).unwrap()))
+        );
+    }
+
+    /// Four tracks, the second one set to Continue into the third
+    fn playlist() -> Vec<AudioTrack> {
+        let mut tracks: Vec<AudioTrack> = (0..4)
+            .map(|id| AudioTrack::new(id, format!("Track {}", id + 1), String::new()))
+            .collect();
+        tracks[1].action = AudioAction::Continue;
+        tracks
+    }
+
+    #[test]
+    fn go_targets_skip_continued_tracks() {
+        let tracks = playlist();
+        assert_eq!(audio_go_targets(&tracks, 0), (0..1, 1));
+        assert_eq!(audio_go_targets(&tracks, 1), (1..3, 3));
+        assert_eq!(audio_go_targets(&tracks, 3), (3..4, 0));
+    }
+
+    #[test]
+    fn continue_chains_stop_at_the_end_of_the_list() {
+        let mut tracks = playlist();
+        tracks[2].action = AudioAction::Continue;
+        tracks[3].action = AudioAction::Continue;
+        assert_eq!(audio_go_targets(&tracks, 1), (1..4, 0));
+        assert_eq!(audio_go_targets(&tracks, 3), (3..4, 0));
+    }
+
+    #[test]
+    fn audio_index_after_each_go() {
+        let mut state = ConsoleState::without_hardware();
+        state.audio_tracks = playlist();
+        let mut landed = Vec::new();
+        for _ in 0..4 {
+            audio_go(&mut state, 4);
+            landed.push(state.audio_index);
+        }
+        // Track 2 continues into track 3, so the GO after it lands on track 4
+        assert_eq!(landed, [1, 3, 0, 1]);
+    }
+
+    #[test]
+    fn out_of_range_index_moves_on() {
+        let tracks = playlist();
+        assert_eq!(audio_go_targets(&tracks, 7), (7..7, 0));
+        assert_eq!(audio_go_targets(&[], 0), (0..0, 0));
+    }
+}